@@ -0,0 +1,4439 @@
+//! Derive macros for [`bevy_enum_event`](https://docs.rs/bevy_enum_event).
+//!
+//! Use them through `bevy_enum_event`, which re-exports them next to the traits the generated
+//! code implements.
+
+use bevy_macro_utils::BevyManifest;
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Display;
+use std::sync::Mutex;
+use syn::{
+    parse_macro_input, spanned::Spanned, visit::Visit, visit_mut::VisitMut, Attribute, Data,
+    DeriveInput, Fields,
+};
+
+/// Converts `PascalCase` or `camelCase` to `snake_case`.
+///
+/// Handles acronyms gracefully: `FSMState` → `fsm_state`, `HTTPServer` → `http_server`
+fn to_snake_case(s: &str) -> String {
+    let mut result = String::new();
+    let chars: Vec<char> = s.chars().collect();
+
+    for (i, &ch) in chars.iter().enumerate() {
+        if ch.is_uppercase() {
+            let is_first = i == 0;
+            let prev_is_lower = i > 0 && chars[i - 1].is_lowercase();
+            let next_is_lower = i + 1 < chars.len() && chars[i + 1].is_lowercase();
+
+            // Add underscore if:
+            // 1. Previous char is lowercase (camelCase -> snake_case)
+            // 2. This is uppercase, next is lowercase, and we're not first (handles acronyms)
+            if !is_first && (prev_is_lower || next_is_lower) {
+                result.push('_');
+            }
+
+            result.push(ch.to_lowercase().next().unwrap());
+        } else {
+            result.push(ch);
+        }
+    }
+    result
+}
+
+struct GenericsUsageCollector<'a> {
+    type_names: &'a [String],
+    lifetime_names: &'a [String],
+    pub used_types: HashSet<String>,
+    pub used_lifetimes: HashSet<String>,
+}
+
+impl<'a> GenericsUsageCollector<'a> {
+    fn new(type_names: &'a [String], lifetime_names: &'a [String]) -> Self {
+        Self {
+            type_names,
+            lifetime_names,
+            used_types: HashSet::new(),
+            used_lifetimes: HashSet::new(),
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for GenericsUsageCollector<'_> {
+    fn visit_type_path(&mut self, type_path: &'ast syn::TypePath) {
+        if type_path.qself.is_none() {
+            if let Some(ident) = type_path.path.get_ident() {
+                let ident_str = ident.to_string();
+                if self.type_names.iter().any(|name| name == &ident_str) {
+                    self.used_types.insert(ident_str);
+                }
+            }
+        }
+        syn::visit::visit_type_path(self, type_path);
+    }
+
+    fn visit_lifetime(&mut self, lifetime: &'ast syn::Lifetime) {
+        let ident_str = lifetime.ident.to_string();
+        if self.lifetime_names.iter().any(|name| name == &ident_str) {
+            self.used_lifetimes.insert(ident_str);
+        }
+        syn::visit::visit_lifetime(self, lifetime);
+    }
+}
+
+/// Collects the leading identifiers of the relative type paths used in field types, so a
+/// generated struct can't silently shadow one of them inside the module.
+#[derive(Default)]
+struct TypeNameCollector {
+    names: HashSet<String>,
+}
+
+impl<'ast> Visit<'ast> for TypeNameCollector {
+    fn visit_type_path(&mut self, type_path: &'ast syn::TypePath) {
+        if type_path.qself.is_none() && type_path.path.leading_colon.is_none() {
+            if let Some(first) = type_path.path.segments.first() {
+                self.names.insert(first.ident.to_string());
+            }
+        }
+        syn::visit::visit_type_path(self, type_path);
+    }
+}
+
+/// Primitive types, which resolve the same from every module.
+const PRIMITIVES: &[&str] = &[
+    "bool", "char", "str", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16",
+    "u32", "u64", "u128", "usize",
+];
+
+/// Whether `name` is a dependency of the calling crate, and so resolves as a crate from every
+/// module.
+fn is_dependency(name: &str) -> bool {
+    if std::env::var_os("CARGO_MANIFEST_DIR").is_none() {
+        return false;
+    }
+    BevyManifest::shared(|manifest| {
+        manifest
+            .maybe_get_path(name)
+            .is_some_and(|path| path.leading_colon.is_none() && path.is_ident(name))
+    })
+}
+
+/// Rewrites paths written next to the enum so they name the same items from inside the
+/// generated module, which imports nothing. Used for the `crate` path and derives, and to find
+/// what has to go through `__scope`.
+///
+/// Relative paths get a `super::` in front and `self::` becomes `super::`. Primitive types, the
+/// enum's generic parameters, an expression's own bindings and paths into `std`, `core`, `alloc`
+/// or a dependency resolve the same from anywhere and are left alone.
+struct ParentScope<'a> {
+    locals: &'a HashSet<String>,
+}
+
+/// A path rewriter that may add segments in front of a path.
+trait RewritePath: VisitMut {
+    /// Rewrites `path` if needed. Returns the number of segments added in front.
+    fn rewrite(&self, path: &mut syn::Path) -> usize;
+}
+
+impl RewritePath for ParentScope<'_> {
+    fn rewrite(&self, path: &mut syn::Path) -> usize {
+        if path.leading_colon.is_some() {
+            return 0;
+        }
+        let len = path.segments.len();
+        let Some(first) = path.segments.first_mut() else {
+            return 0;
+        };
+        let name = first.ident.to_string();
+        let span = first.ident.span();
+        match name.as_str() {
+            "crate" | "Self" | "std" | "core" | "alloc" => 0,
+            // A lone `self` is the receiver of a computed target
+            "self" if len > 1 => {
+                first.ident = syn::Ident::new("super", span);
+                0
+            }
+            "self" => 0,
+            _ if PRIMITIVES.contains(&name.as_str())
+                || self.locals.contains(&name)
+                || is_dependency(&name) =>
+            {
+                0
+            }
+            _ => {
+                let rest = std::mem::take(&mut path.segments);
+                path.segments =
+                    std::iter::once(syn::PathSegment::from(syn::Ident::new("super", span)))
+                        .chain(rest)
+                        .collect();
+                1
+            }
+        }
+    }
+}
+
+impl VisitMut for ParentScope<'_> {
+    fn visit_path_mut(&mut self, path: &mut syn::Path) {
+        self.rewrite(path);
+        syn::visit_mut::visit_path_mut(self, path);
+    }
+
+    fn visit_type_path_mut(&mut self, type_path: &mut syn::TypePath) {
+        rewrite_qualified(self, type_path.qself.as_mut(), &mut type_path.path);
+    }
+
+    fn visit_expr_path_mut(&mut self, expr_path: &mut syn::ExprPath) {
+        rewrite_qualified(self, expr_path.qself.as_mut(), &mut expr_path.path);
+    }
+
+    // Macros are resolved textually, and attributes belong to the tools that read them
+    fn visit_macro_mut(&mut self, _macro: &mut syn::Macro) {}
+
+    fn visit_attribute_mut(&mut self, _attribute: &mut Attribute) {}
+}
+
+/// Moves paths written next to the enum into the `__scope` module two levels further down,
+/// which sees the enum's scope through a glob import. Only `self::` and `super::` paths change.
+struct ScopeDepth;
+
+impl RewritePath for ScopeDepth {
+    fn rewrite(&self, path: &mut syn::Path) -> usize {
+        if path.leading_colon.is_some() || path.segments.len() < 2 {
+            return 0;
+        }
+        let first = &mut path.segments[0].ident;
+        let span = first.span();
+        let added = match first.to_string().as_str() {
+            "self" => {
+                *first = syn::Ident::new("super", span);
+                1
+            }
+            "super" => 2,
+            _ => return 0,
+        };
+        let rest = std::mem::take(&mut path.segments);
+        path.segments = (0..added)
+            .map(|_| syn::PathSegment::from(syn::Ident::new("super", span)))
+            .chain(rest)
+            .collect();
+        added
+    }
+}
+
+impl VisitMut for ScopeDepth {
+    fn visit_path_mut(&mut self, path: &mut syn::Path) {
+        self.rewrite(path);
+        syn::visit_mut::visit_path_mut(self, path);
+    }
+
+    fn visit_type_path_mut(&mut self, type_path: &mut syn::TypePath) {
+        rewrite_qualified(self, type_path.qself.as_mut(), &mut type_path.path);
+    }
+
+    fn visit_expr_path_mut(&mut self, expr_path: &mut syn::ExprPath) {
+        rewrite_qualified(self, expr_path.qself.as_mut(), &mut expr_path.path);
+    }
+
+    fn visit_macro_mut(&mut self, _macro: &mut syn::Macro) {}
+
+    fn visit_attribute_mut(&mut self, _attribute: &mut Attribute) {}
+}
+
+/// `node` moved into the `__scope` module.
+fn in_scope<T: Clone>(node: &T, visit: fn(&mut ScopeDepth, &mut T)) -> T {
+    let mut node = node.clone();
+    visit(&mut ScopeDepth, &mut node);
+    node
+}
+
+/// Name of a hidden item of the `__scope` module, `__Scored_0`.
+fn hidden_ident(name: impl Display) -> syn::Ident {
+    syn::Ident::new(&format!("__{name}"), proc_macro2::Span::call_site())
+}
+
+/// Rewrites a path that may start with `<T as Trait>`, where only the trait is relative to the
+/// enum's scope.
+fn rewrite_qualified(
+    scope: &mut impl RewritePath,
+    qself: Option<&mut syn::QSelf>,
+    path: &mut syn::Path,
+) {
+    let Some(qself) = qself else {
+        scope.visit_path_mut(path);
+        return;
+    };
+    scope.visit_type_mut(&mut qself.ty);
+    if qself.position > 0 {
+        qself.position += scope.rewrite(path);
+    }
+    for segment in &mut path.segments {
+        scope.visit_path_arguments_mut(&mut segment.arguments);
+    }
+}
+
+/// Collects the enum's generic parameters that a type mentions.
+struct ParamCollector {
+    names: HashSet<String>,
+}
+
+impl<'ast> Visit<'ast> for ParamCollector {
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        if let (None, Some(first)) = (path.leading_colon, path.segments.first()) {
+            self.names.insert(first.ident.to_string());
+        }
+        syn::visit::visit_path(self, path);
+    }
+
+    fn visit_lifetime(&mut self, lifetime: &'ast syn::Lifetime) {
+        self.names.insert(lifetime.to_string());
+    }
+}
+
+/// The parameters of `generics` that `ty` mentions, without bounds or defaults, for a type alias
+/// of `ty`.
+fn used_generics(generics: &syn::Generics, ty: &syn::Type) -> syn::Generics {
+    let mut collector = ParamCollector {
+        names: HashSet::new(),
+    };
+    collector.visit_type(ty);
+    let params = generics
+        .params
+        .iter()
+        .filter_map(|param| {
+            let mut param = param.clone();
+            let name = match &mut param {
+                syn::GenericParam::Type(param) => {
+                    param.bounds.clear();
+                    param.colon_token = None;
+                    param.default = None;
+                    param.eq_token = None;
+                    param.ident.to_string()
+                }
+                syn::GenericParam::Lifetime(param) => {
+                    param.bounds.clear();
+                    param.colon_token = None;
+                    param.lifetime.to_string()
+                }
+                syn::GenericParam::Const(param) => {
+                    param.default = None;
+                    param.eq_token = None;
+                    param.ident.to_string()
+                }
+            };
+            collector.names.contains(&name).then_some(param)
+        })
+        .collect();
+    syn::Generics {
+        lt_token: Some(Default::default()),
+        params,
+        gt_token: Some(Default::default()),
+        where_clause: None,
+    }
+}
+
+/// Whether `bound` is a relaxed bound such as `?Sized`, which can't be bundled into a trait.
+fn is_relaxed(bound: &syn::TypeParamBound) -> bool {
+    matches!(
+        bound,
+        syn::TypeParamBound::Trait(syn::TraitBound {
+            modifier: syn::TraitBoundModifier::Maybe(_),
+            ..
+        })
+    )
+}
+
+/// `generics` with only the bounds that name nothing: lifetime bounds and relaxed bounds.
+fn unbounded(generics: &syn::Generics) -> syn::Generics {
+    let mut generics = generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds = std::mem::take(&mut param.bounds)
+            .into_iter()
+            .filter(is_relaxed)
+            .collect();
+        if param.bounds.is_empty() {
+            param.colon_token = None;
+        }
+    }
+    if let Some(where_clause) = &mut generics.where_clause {
+        where_clause.predicates = std::mem::take(&mut where_clause.predicates)
+            .into_iter()
+            .filter_map(|predicate| match predicate {
+                syn::WherePredicate::Type(mut predicate) => {
+                    predicate.bounds = std::mem::take(&mut predicate.bounds)
+                        .into_iter()
+                        .filter(is_relaxed)
+                        .collect();
+                    (!predicate.bounds.is_empty()).then_some(syn::WherePredicate::Type(predicate))
+                }
+                predicate => Some(predicate),
+            })
+            .collect();
+    }
+    generics
+}
+
+/// The trait bounds of `generics`, inline ones first, without relaxed bounds. Binders such as
+/// `for<'a>` move onto the bounds.
+fn bound_predicates(generics: &syn::Generics) -> Vec<syn::PredicateType> {
+    let inline = generics.type_params().map(|param| {
+        let ident = &param.ident;
+        syn::PredicateType {
+            lifetimes: None,
+            bounded_ty: syn::parse_quote! { #ident },
+            colon_token: Default::default(),
+            bounds: param.bounds.clone(),
+        }
+    });
+    let where_clause = generics
+        .where_clause
+        .iter()
+        .flat_map(|where_clause| &where_clause.predicates)
+        .filter_map(|predicate| match predicate {
+            syn::WherePredicate::Type(predicate) => Some(predicate.clone()),
+            _ => None,
+        });
+    inline
+        .chain(where_clause)
+        .filter_map(|mut predicate: syn::PredicateType| {
+            let binder = predicate.lifetimes.take();
+            predicate.bounds = std::mem::take(&mut predicate.bounds)
+                .into_iter()
+                .filter(|bound| !is_relaxed(bound))
+                .map(|mut bound| {
+                    if let syn::TypeParamBound::Trait(bound) = &mut bound {
+                        bound.lifetimes = bound.lifetimes.take().or(binder.clone());
+                    }
+                    bound
+                })
+                .collect();
+            (!predicate.bounds.is_empty()).then_some(predicate)
+        })
+        .collect()
+}
+
+/// Collects the names an expression binds itself, in closures, `let`s and patterns.
+#[derive(Default)]
+struct BindingCollector {
+    names: HashSet<String>,
+}
+
+impl<'ast> Visit<'ast> for BindingCollector {
+    fn visit_pat_ident(&mut self, pat_ident: &'ast syn::PatIdent) {
+        self.names.insert(pat_ident.ident.to_string());
+        syn::visit::visit_pat_ident(self, pat_ident);
+    }
+}
+
+/// Collects the errors of one derive, so that every mistake is reported in a single pass.
+#[derive(Default)]
+struct Errors(Option<syn::Error>);
+
+impl Errors {
+    fn push(&mut self, error: syn::Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    /// Reports `message` at `tokens` unless `condition` holds.
+    fn check(&mut self, condition: bool, tokens: impl quote::ToTokens, message: impl Display) {
+        if !condition {
+            self.push(syn::Error::new_spanned(tokens, message));
+        }
+    }
+
+    /// Reports `message` at `span` unless `condition` holds.
+    fn check_at(&mut self, condition: bool, span: Span, message: impl Display) {
+        if !condition {
+            self.push(syn::Error::new(span, message));
+        }
+    }
+
+    fn finish(self) -> syn::Result<()> {
+        self.0.map_or(Ok(()), Err)
+    }
+}
+
+/// Reports two generated structs, or a struct and another item of the generated module, that
+/// would share a name.
+fn check_struct_names(
+    variants: &syn::punctuated::Punctuated<syn::Variant, syn::Token![,]>,
+    layout: &Layout,
+    shapes: &[VariantShape],
+    reserved: &[(String, Option<&str>)],
+    errors: &mut Errors,
+) {
+    // Only names still unqualified inside the module can be shadowed
+    let mut field_types = TypeNameCollector::default();
+    for variant in variants {
+        for (index, field) in variant.fields.iter().enumerate() {
+            field_types.visit_type(&layout.field_type(&variant.ident, index, &field.ty));
+        }
+    }
+
+    for (index, shape) in shapes.iter().enumerate() {
+        let variant_ident = &shape.ident;
+        let struct_ident = &shape.struct_ident;
+        let struct_name = struct_ident.to_string();
+
+        if let Some(other) = shapes[..index]
+            .iter()
+            .find(|other| other.struct_ident == *struct_ident)
+        {
+            errors.push(syn::Error::new_spanned(
+                variant_ident,
+                format!(
+                    "variants `{}` and `{variant_ident}` both generate a struct named `{struct_name}`; use #[enum_event(rename = \"...\")] on one of them",
+                    other.ident
+                ),
+            ));
+        }
+        if let Some((_, key)) = reserved.iter().find(|(name, _)| *name == struct_name) {
+            let rename_item = key
+                .map(|key| {
+                    format!(", or name the {key} with #[enum_event(rename_items({key} = \"...\"))]")
+                })
+                .unwrap_or_default();
+            errors.push(syn::Error::new_spanned(
+                variant_ident,
+                format!("variant `{variant_ident}` generates a struct named `{struct_name}`, which clashes with another item named `{struct_name}` in the same scope; use #[enum_event(rename = \"...\")] or a prefix/suffix{rename_item}"),
+            ));
+        }
+        errors.check(
+            !field_types.names.contains(&struct_name),
+            variant_ident,
+            format!("variant `{variant_ident}` generates a struct named `{struct_name}`, which would shadow the type `{struct_name}` used in the enum's fields; use #[enum_event(rename = \"...\")] or a prefix/suffix"),
+        );
+    }
+}
+
+fn path_ends_with_ident(path: &syn::Path, ident: &str) -> bool {
+    path.segments
+        .last()
+        .is_some_and(|segment| segment.ident == ident)
+}
+
+/// Variant attributes copied onto the generated structs unless excluded: docs, deprecation and
+/// lint levels. `#[cfg]` is always copied, everything else needs `forward_attrs(...)`, since
+/// other variant attributes are mostly helpers of the enum's derives and invalid on a struct.
+const FORWARDED_ATTRS: &[&str] = &[
+    "doc",
+    "deprecated",
+    "allow",
+    "warn",
+    "deny",
+    "expect",
+    "forbid",
+];
+
+/// Keys accepted by `#[enum_event(...)]` on the enum.
+const ENUM_KEYS: &[&str] = &[
+    "propagate",
+    "auto_propagate",
+    "inject_entity",
+    "module",
+    "flat",
+    "prefix",
+    "suffix",
+    "vis",
+    "crate",
+    "also",
+    "deref",
+    "no_deref",
+    "derive",
+    "no_clone",
+    "no_debug",
+    "forward_derives",
+    "forward_attrs",
+    "exclude_attrs",
+    "rename_items",
+];
+
+/// Helper items that `rename_items(...)` can name, keyed by their default name in snake_case.
+const ITEM_KEYS: &[&str] = &["plugin", "kind", "any", "reader", "writer", "write_order"];
+
+/// Keys accepted by `#[enum_event(...)]` on a variant.
+const VARIANT_KEYS: &[&str] = &[
+    "propagate",
+    "auto_propagate",
+    "target",
+    "rename",
+    "vis",
+    "skip",
+    "kind",
+    "also",
+    "deref",
+    "no_deref",
+    "derive",
+    "no_clone",
+    "no_debug",
+    "forward_attrs",
+    "exclude_attrs",
+];
+
+/// Keys accepted by `#[enum_event(...)]` on a field.
+const FIELD_KEYS: &[&str] = &["target", "deref", "deref_mut", "vis", "default"];
+
+/// The keys given in the `enum_event` attributes of one item, with their spans.
+#[derive(Default)]
+struct AttrKeys(Vec<(String, Span)>);
+
+impl AttrKeys {
+    fn record(&mut self, path: &syn::Path) {
+        if let Some(segment) = path.segments.last() {
+            self.0.push((segment.ident.to_string(), path.span()));
+        }
+    }
+
+    /// The span of the first of `keys` that was given.
+    fn span(&self, keys: &[&str]) -> Option<Span> {
+        self.0
+            .iter()
+            .find(|(key, _)| keys.contains(&key.as_str()))
+            .map(|(_, span)| *span)
+    }
+}
+
+/// The error for a key that `keys` doesn't contain, pointing to the nearest valid key or to the
+/// level the key belongs to.
+fn unknown_key(meta: &syn::meta::ParseNestedMeta, level: &str, keys: &[&str]) -> syn::Error {
+    let key = meta
+        .path
+        .segments
+        .last()
+        .map(|segment| segment.ident.to_string())
+        .unwrap_or_default();
+    let levels: Vec<_> = [
+        ("the enum", ENUM_KEYS),
+        ("a variant", VARIANT_KEYS),
+        ("a field", FIELD_KEYS),
+    ]
+    .into_iter()
+    .filter(|(_, keys)| keys.contains(&key.as_str()))
+    .map(|(level, _)| level)
+    .collect();
+    let nearest = keys
+        .iter()
+        .map(|known| (edit_distance(&key, known), *known))
+        .filter(|(distance, _)| *distance <= (key.len() / 3).max(1))
+        .min();
+
+    if !levels.is_empty() {
+        meta.error(format!(
+            "`{key}` goes on {}, not on {level}",
+            levels.join(" or ")
+        ))
+    } else if let Some((_, nearest)) = nearest {
+        meta.error(format!(
+            "unknown enum_event attribute `{key}` on {level}; did you mean `{nearest}`?"
+        ))
+    } else {
+        meta.error(format!(
+            "unknown enum_event attribute `{key}` on {level}; expected one of {}",
+            keys.iter()
+                .map(|known| format!("`{known}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    }
+}
+
+/// Levenshtein distance between two keys.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a_char != *b_char);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[derive(Default)]
+struct FieldAttrInfo {
+    /// Every marker given, including the bare `#[deref]` and `#[event_target]` attributes.
+    keys: AttrKeys,
+    passthrough_attrs: Vec<Attribute>,
+    has_deref: bool,
+    has_deref_mut: bool,
+    is_event_target: bool,
+    vis: Option<syn::Visibility>,
+    /// `default` or `default = expr`, which makes the field optional in the builder.
+    default: Option<syn::Expr>,
+}
+
+#[derive(Default)]
+struct VariantAttrInfo {
+    keys: AttrKeys,
+    propagate_value: Option<proc_macro2::TokenStream>,
+    has_auto_propagate: bool,
+    rename: Option<syn::Ident>,
+    vis: Option<syn::Visibility>,
+    derives: DeriveOptions,
+    skip: bool,
+    /// `kind = message | event | entity`, overriding the derive's kind.
+    kind: Option<EventKind>,
+    /// `also = message | event | entity`, overriding the enum's `also`.
+    also: Option<(EventKind, Span)>,
+    /// `deref = <bool>` or `no_deref`, overriding the enum's policy.
+    deref: Option<(bool, Span)>,
+    /// `target = expr`, the entity event target computed from `&self`.
+    target: Option<syn::Expr>,
+    /// Attributes that may be copied onto the generated struct.
+    passthrough_attrs: Vec<Attribute>,
+    forward_attrs: Vec<syn::Path>,
+    exclude_attrs: Vec<syn::Path>,
+}
+
+/// Standard derives of the generated structs, adjusted by `derive(...)`, `no_clone` and `no_debug`.
+#[derive(Default)]
+struct DeriveOptions {
+    extra: Vec<syn::Path>,
+    no_clone: bool,
+    no_debug: bool,
+}
+
+impl DeriveOptions {
+    /// Parses one of the derive keys. Returns `false` if `meta` is some other key.
+    fn parse(&mut self, meta: &syn::meta::ParseNestedMeta) -> syn::Result<bool> {
+        if path_ends_with_ident(&meta.path, "derive") {
+            meta.parse_nested_meta(|derive| {
+                self.extra.push(derive.path);
+                Ok(())
+            })?;
+        } else if path_ends_with_ident(&meta.path, "no_clone") {
+            self.no_clone = true;
+        } else if path_ends_with_ident(&meta.path, "no_debug") {
+            self.no_debug = true;
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Derives of one variant struct: `Clone, Debug` (plus `Copy, Default` for unit variants),
+    /// then the forwarded enum derives, then the enum-level and variant-level additions.
+    fn struct_derives(
+        &self,
+        variant: &Self,
+        is_unit: bool,
+        forwarded: &[syn::Path],
+    ) -> Vec<syn::Path> {
+        let no_clone = self.no_clone || variant.no_clone;
+        let no_debug = self.no_debug || variant.no_debug;
+        let mut standard: Vec<syn::Path> = Vec::new();
+        if !no_clone {
+            standard.push(syn::parse_quote!(Clone));
+            if is_unit {
+                standard.push(syn::parse_quote!(Copy));
+            }
+        }
+        if !no_debug {
+            standard.push(syn::parse_quote!(Debug));
+        }
+        if is_unit {
+            standard.push(syn::parse_quote!(Default));
+        }
+
+        let mut derives: Vec<syn::Path> = Vec::new();
+        for path in standard
+            .into_iter()
+            .chain(forwarded.iter().cloned())
+            .chain(self.extra.iter().cloned())
+            .chain(variant.extra.iter().cloned())
+        {
+            let Some(name) = path
+                .segments
+                .last()
+                .map(|segment| segment.ident.to_string())
+            else {
+                continue;
+            };
+            let opted_out = match name.as_str() {
+                "Clone" | "Copy" => no_clone,
+                "Debug" => no_debug,
+                _ => false,
+            };
+            if !opted_out
+                && !derives
+                    .iter()
+                    .any(|derive| path_ends_with_ident(derive, &name))
+            {
+                derives.push(path);
+            }
+        }
+        derives
+    }
+}
+
+/// The enum's own `#[derive(...)]` list, minus this crate's macros, for `forward_derives`.
+fn enum_derives(attrs: &[Attribute]) -> Vec<syn::Path> {
+    const OWN_DERIVES: [&str; 3] = ["EnumEvent", "EnumMessage", "EnumEntityEvent"];
+
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| {
+            attr.parse_args_with(
+                syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated,
+            )
+            .ok()
+        })
+        .flatten()
+        .filter(|path| {
+            !OWN_DERIVES
+                .iter()
+                .any(|own| path_ends_with_ident(path, own))
+        })
+        .collect()
+}
+
+/// Paths to the Bevy crates the generated code refers to.
+#[derive(Clone)]
+struct BevyPaths {
+    /// `bevy_ecs`, either directly or through the `bevy` facade.
+    ecs: proc_macro2::TokenStream,
+    /// `bevy_app`, needed for the generated `Plugin`.
+    app: Option<proc_macro2::TokenStream>,
+    /// Module exporting the `Deref`/`DerefMut` derives.
+    deref: Option<proc_macro2::TokenStream>,
+    /// `bevy_reflect`, for the bounds of registering generic `Reflect` structs.
+    reflect: Option<proc_macro2::TokenStream>,
+}
+
+impl BevyPaths {
+    /// Paths below the `bevy` facade crate, or a re-export of it.
+    fn facade(bevy: &syn::Path) -> Self {
+        Self {
+            ecs: quote! { #bevy::ecs },
+            app: Some(quote! { #bevy::app }),
+            deref: Some(quote! { #bevy::prelude }),
+            reflect: Some(quote! { #bevy::reflect }),
+        }
+    }
+
+    /// Resolves `#[enum_event(crate = path)]`, or detects the Bevy crates from the caller's
+    /// `Cargo.toml` when it is not given.
+    ///
+    /// An explicit path names the `bevy` crate, or the ECS crate if it ends in `bevy_ecs` or
+    /// `ecs`. Without one, `bevy` is used unless the caller depends on `bevy_ecs` but not on `bevy`.
+    fn resolve(crate_path: Option<&syn::Path>) -> Self {
+        if let Some(path) = crate_path {
+            let is_ecs =
+                path_ends_with_ident(path, "bevy_ecs") || path_ends_with_ident(path, "ecs");
+            return if is_ecs {
+                Self {
+                    ecs: quote! { #path },
+                    app: None,
+                    deref: None,
+                    reflect: None,
+                }
+            } else {
+                Self::facade(path)
+            };
+        }
+
+        let facade = Self::facade(&syn::parse_quote!(::bevy));
+        if std::env::var_os("CARGO_MANIFEST_DIR").is_none() {
+            return facade;
+        }
+        BevyManifest::shared(|manifest| {
+            if manifest.maybe_get_path("bevy").is_some() {
+                return facade;
+            }
+            // Dependencies are named from the crate root, so no item of the caller can shadow them
+            let absolute = |mut path: syn::Path| {
+                path.leading_colon = Some(Default::default());
+                quote! { #path }
+            };
+            match manifest.maybe_get_path("bevy_ecs") {
+                Some(ecs) => Self {
+                    ecs: absolute(ecs),
+                    app: manifest.maybe_get_path("bevy_app").map(absolute),
+                    deref: manifest.maybe_get_path("bevy_derive").map(absolute),
+                    reflect: manifest.maybe_get_path("bevy_reflect").map(absolute),
+                },
+                None => facade,
+            }
+        })
+    }
+}
+
+/// Where the generated items live and what the family-wide helper items are called.
+///
+/// By default everything goes into a snake_case module and the helpers have short names
+/// (`game_event::Plugin`). In flat mode the items are emitted next to the enum instead, so the
+/// helpers are prefixed with the enum name (`GameEventPlugin`) to keep them apart.
+struct Layout {
+    /// The generated module, or `None` in flat mode.
+    module: Option<syn::Ident>,
+    /// Visibility of the family as seen from the enum's scope.
+    vis: syn::Visibility,
+    plugin: syn::Ident,
+    kind: syn::Ident,
+    any: syn::Ident,
+    reader: syn::Ident,
+    writer: syn::Ident,
+    write_order: syn::Ident,
+    /// Paths to the Bevy crates as seen from next to the enum.
+    bevy: BevyPaths,
+    /// Paths to the Bevy crates as seen from the generated items, the same as `bevy` in flat
+    /// mode.
+    module_bevy: BevyPaths,
+    /// Paths to the Bevy crates as seen from the `__io` module below the generated module.
+    io_bevy: BevyPaths,
+    /// In flat mode with a `crate` path, the `bevy_ecs` path as seen from the hidden module that
+    /// aliases it for Bevy's derives next to the enum, see `flat_ecs_import`.
+    flat_ecs: Option<proc_macro2::TokenStream>,
+    /// Generic parameters of the enum, which need no rewriting inside the module.
+    params: HashSet<String>,
+    /// The enum's generics, which the hidden items of `__scope` repeat.
+    generics: syn::Generics,
+}
+
+impl Layout {
+    fn new(
+        enum_name: &syn::Ident,
+        module: Option<syn::Ident>,
+        vis: syn::Visibility,
+        crate_path: Option<&syn::Path>,
+        generics: &syn::Generics,
+        item_names: &[(String, syn::Ident)],
+    ) -> Self {
+        let prefix = if module.is_some() {
+            String::new()
+        } else {
+            enum_name.to_string()
+        };
+        let item = |name: &str| {
+            let key = to_snake_case(name);
+            match item_names.iter().rev().find(|(given, _)| *given == key) {
+                Some((_, ident)) => ident.clone(),
+                None => syn::Ident::new(&format!("{prefix}{name}"), enum_name.span()),
+            }
+        };
+        let params = generics
+            .type_params()
+            .map(|param| param.ident.to_string())
+            .chain(generics.const_params().map(|param| param.ident.to_string()))
+            .collect();
+        let mut layout = Self {
+            plugin: item("Plugin"),
+            kind: item("Kind"),
+            any: item("Any"),
+            reader: item("Reader"),
+            writer: item("Writer"),
+            write_order: item("WriteOrder"),
+            module,
+            vis,
+            bevy: BevyPaths::resolve(crate_path),
+            module_bevy: BevyPaths::resolve(crate_path),
+            io_bevy: BevyPaths::resolve(crate_path),
+            flat_ecs: None,
+            params,
+            generics: generics.clone(),
+        };
+        if let (None, Some(crate_path)) = (&layout.module, crate_path) {
+            if !is_dependency("bevy_ecs") {
+                let mut nested_path = crate_path.clone();
+                ParentScope {
+                    locals: &layout.params,
+                }
+                .visit_path_mut(&mut nested_path);
+                layout.flat_ecs = Some(BevyPaths::resolve(Some(&nested_path)).ecs);
+            }
+        }
+        if let Some(crate_path) = crate_path {
+            let mut module_path = crate_path.clone();
+            layout.in_module(|scope| scope.visit_path_mut(&mut module_path));
+            layout.module_bevy = BevyPaths::resolve(Some(&module_path));
+            layout.in_module(|scope| scope.visit_path_mut(&mut module_path));
+            layout.io_bevy = BevyPaths::resolve(Some(&module_path));
+        }
+        layout
+    }
+
+    /// Runs `rewrite` on something written next to the enum, so it means the same inside the
+    /// generated module. Nothing changes in flat mode.
+    fn in_module(&self, rewrite: impl FnOnce(&mut ParentScope)) {
+        if self.module.is_some() {
+            rewrite(&mut ParentScope {
+                locals: &self.params,
+            });
+        }
+    }
+
+    /// Whether `node`, written next to the enum, names anything relative to the enum's scope.
+    ///
+    /// The generated module can't reach such names on its own: `super::` misses the prelude and
+    /// whatever the enum's scope takes in through glob imports. They are resolved by hidden items
+    /// in its `__scope` module instead, see `generate_scope`. Items declared inside a function
+    /// body stay out of reach either way.
+    fn is_relative<T: Clone + quote::ToTokens>(
+        &self,
+        node: &T,
+        rewrite: impl FnOnce(&mut ParentScope, &mut T),
+    ) -> bool {
+        let mut rewritten = node.clone();
+        self.in_module(|scope| rewrite(scope, &mut rewritten));
+        quote!(#rewritten).to_string() != quote!(#node).to_string()
+    }
+
+    /// `ty` as seen from the generated module, through the hidden alias `__scope::__{alias}` if
+    /// it names anything relative to the enum's scope. Nothing changes in flat mode.
+    fn module_type(&self, ty: &syn::Type, alias: impl Display) -> syn::Type {
+        if !self.is_relative(ty, |scope, ty| scope.visit_type_mut(ty)) {
+            return ty.clone();
+        }
+        let alias = hidden_ident(alias);
+        let generics = used_generics(&self.generics, ty);
+        let (_, ty_generics, _) = generics.split_for_impl();
+        syn::parse_quote_spanned! {ty.span()=> __scope::#alias #ty_generics }
+    }
+
+    /// The hidden alias behind `module_type`.
+    fn type_alias(&self, ty: &syn::Type, alias: impl Display) -> proc_macro2::TokenStream {
+        if !self.is_relative(ty, |scope, ty| scope.visit_type_mut(ty)) {
+            return quote! {};
+        }
+        let alias = hidden_ident(alias);
+        let generics = used_generics(&self.generics, ty);
+        let ty = in_scope(ty, ScopeDepth::visit_type_mut);
+        quote! {
+            #[allow(non_camel_case_types)]
+            pub type #alias #generics = #ty;
+        }
+    }
+
+    /// Type of field `index` of `variant` as seen from the generated module.
+    fn field_type(&self, variant: &syn::Ident, index: usize, ty: &syn::Type) -> syn::Type {
+        self.module_type(ty, format_args!("{variant}_{index}"))
+    }
+
+    /// Whether `module_generics` of `generics` names hidden items of `__scope`.
+    fn generics_use_scope(&self, generics: &syn::Generics) -> bool {
+        let relative_default = generics.params.iter().any(|param| match param {
+            syn::GenericParam::Type(param) => param.default.as_ref().is_some_and(|default| {
+                self.is_relative(default, |scope, ty| scope.visit_type_mut(ty))
+            }),
+            syn::GenericParam::Const(param) => param
+                .default
+                .as_ref()
+                .is_some_and(|default| self.is_relative_expr(default)),
+            syn::GenericParam::Lifetime(_) => false,
+        });
+        self.module.is_some()
+            && (relative_default
+                || bound_predicates(generics)
+                    .iter()
+                    .any(|predicate| self.is_relative_bound(predicate)))
+    }
+
+    /// Whether Bevy's own derives inside the generated module need `bevy_ecs` imported: they
+    /// fall back to it when the caller depends on neither `bevy` nor `bevy_ecs`, which only a
+    /// re-export can provide.
+    fn needs_ecs_import(&self) -> bool {
+        self.module.is_some() && !is_dependency("bevy") && !is_dependency("bevy_ecs")
+    }
+
+    /// The `bevy_ecs` alias for Bevy's derives on flat structs, which resolve it next to the
+    /// enum.
+    ///
+    /// A plain `use` would clash with a second flat enum in the same module, so the alias sits
+    /// in a hidden module and comes in through a glob import: globs naming the same crate don't
+    /// conflict, and an alias written by the user shadows them.
+    fn flat_ecs_import(&self, enum_name: &syn::Ident) -> proc_macro2::TokenStream {
+        let Some(ecs) = &self.flat_ecs else {
+            return quote! {};
+        };
+        let module = syn::Ident::new(
+            &format!("__{}_bevy_ecs", to_snake_case(&enum_name.to_string())),
+            enum_name.span(),
+        );
+        quote! {
+            #[doc(hidden)]
+            mod #module {
+                #[allow(unused_imports)]
+                pub(super) use #ecs as bevy_ecs;
+            }
+            #[allow(unused_imports)]
+            use self::#module::*;
+        }
+    }
+
+    /// `generics` as seen from the generated module.
+    ///
+    /// Bounds naming anything relative to the enum's scope are bundled into hidden traits,
+    /// implemented for everything that meets them.
+    fn module_generics(&self, generics: &syn::Generics) -> syn::Generics {
+        if self.module.is_none() {
+            return generics.clone();
+        }
+        let mut module_generics = unbounded(generics);
+        for param in &mut module_generics.params {
+            match param {
+                syn::GenericParam::Type(param) => {
+                    param.default = param.default.as_ref().map(|default| {
+                        self.module_type(default, format_args!("{}_default", param.ident))
+                    });
+                }
+                syn::GenericParam::Const(param) => {
+                    // Const defaults are blocks unless they are literals or plain names
+                    param.default = param.default.as_ref().map(|default| {
+                        let default =
+                            self.module_const(default, format_args!("{}_default", param.ident));
+                        syn::parse_quote! { { #default } }
+                    });
+                }
+                syn::GenericParam::Lifetime(_) => {}
+            }
+        }
+        let predicates = &mut module_generics.make_where_clause().predicates;
+        let (_, ty_generics, _) = generics.split_for_impl();
+        for (index, predicate) in bound_predicates(generics).iter().enumerate() {
+            if !self.is_relative_bound(predicate) {
+                predicates.push(syn::WherePredicate::Type(predicate.clone()));
+                continue;
+            }
+            let bounded_ty =
+                self.module_type(&predicate.bounded_ty, format_args!("bound{index}_type"));
+            let bound = hidden_ident(format_args!("Bound{index}"));
+            predicates.push(syn::parse_quote! { #bounded_ty: __scope::#bound #ty_generics });
+        }
+        if predicates.is_empty() {
+            module_generics.where_clause = None;
+        }
+        module_generics
+    }
+
+    /// Whether the bounds of `predicate` name anything relative to the enum's scope, and so are
+    /// bundled into a hidden trait.
+    fn is_relative_bound(&self, predicate: &syn::PredicateType) -> bool {
+        self.is_relative(predicate, |scope, predicate| {
+            scope.visit_predicate_type_mut(predicate)
+        })
+    }
+
+    /// Whether `expr` names anything relative to the enum's scope. Names the expression binds
+    /// itself are local.
+    fn is_relative_expr(&self, expr: &syn::Expr) -> bool {
+        let mut bindings = BindingCollector::default();
+        bindings.visit_expr(expr);
+        let locals = &bindings.names | &self.params;
+        self.is_relative(expr, |_, expr| {
+            ParentScope { locals: &locals }.visit_expr_mut(expr)
+        })
+    }
+
+    /// Constant `expr` as seen from the generated module, through the hidden constant
+    /// `__scope::__{name}` if it names anything relative to the enum's scope.
+    fn module_const(&self, expr: &syn::Expr, name: impl Display) -> syn::Expr {
+        if !self.is_relative_expr(expr) {
+            return expr.clone();
+        }
+        let name = hidden_ident(name);
+        syn::parse_quote_spanned! {expr.span()=> __scope::#name }
+    }
+
+    /// The hidden constant behind `module_const`, of type `ty`.
+    fn hidden_const(
+        &self,
+        expr: &syn::Expr,
+        name: impl Display,
+        ty: &syn::Type,
+    ) -> proc_macro2::TokenStream {
+        if !self.is_relative_expr(expr) {
+            return quote! {};
+        }
+        let name = hidden_ident(name);
+        let ty = in_scope(ty, ScopeDepth::visit_type_mut);
+        let expr = in_scope(expr, ScopeDepth::visit_expr_mut);
+        quote! {
+            #[allow(non_upper_case_globals)]
+            pub const #name: #ty = #expr;
+        }
+    }
+
+    /// `expr` as seen from the generated module, through a call to the hidden function
+    /// `__scope::__{name}` if it names anything relative to the enum's scope.
+    fn module_expr(&self, expr: &syn::Expr, name: impl Display) -> syn::Expr {
+        if !self.is_relative_expr(expr) {
+            return expr.clone();
+        }
+        let name = hidden_ident(name);
+        let args: Vec<_> = self
+            .generics
+            .params
+            .iter()
+            .filter_map(|param| match param {
+                syn::GenericParam::Type(param) => Some(&param.ident),
+                syn::GenericParam::Const(param) => Some(&param.ident),
+                syn::GenericParam::Lifetime(_) => None,
+            })
+            .collect();
+        if args.is_empty() {
+            syn::parse_quote_spanned! {expr.span()=> __scope::#name() }
+        } else {
+            syn::parse_quote_spanned! {expr.span()=> __scope::#name::<#(#args),*>() }
+        }
+    }
+
+    /// The hidden function behind `module_expr`, returning `ty`.
+    fn hidden_fn(
+        &self,
+        expr: &syn::Expr,
+        name: impl Display,
+        ty: &syn::Type,
+    ) -> proc_macro2::TokenStream {
+        if !self.is_relative_expr(expr) {
+            return quote! {};
+        }
+        let name = hidden_ident(name);
+        let generics = in_scope(&self.generics, ScopeDepth::visit_generics_mut);
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        let ty = in_scope(ty, ScopeDepth::visit_type_mut);
+        let expr = in_scope(expr, ScopeDepth::visit_expr_mut);
+        quote! {
+            #[allow(non_snake_case)]
+            pub fn #name #impl_generics() -> #ty #where_clause {
+                #expr
+            }
+        }
+    }
+
+    /// A derive given by the user, as seen from the generated module. The built-in derives come
+    /// from the prelude, which `super::` can't reach, so they stay as written.
+    fn module_derive(&self, path: &syn::Path) -> syn::Path {
+        const BUILTIN: &[&str] = &[
+            "Clone",
+            "Copy",
+            "Debug",
+            "Default",
+            "Eq",
+            "Hash",
+            "Ord",
+            "PartialEq",
+            "PartialOrd",
+        ];
+
+        let mut path = path.clone();
+        if !BUILTIN.iter().any(|builtin| path.is_ident(builtin)) {
+            self.in_module(|scope| scope.visit_path_mut(&mut path));
+        }
+        path
+    }
+
+    /// Emits `vis`, written relative to the enum's scope, for an item of the generated module.
+    ///
+    /// Items inside the module are one level deeper, so private and `super`/`self`-relative
+    /// visibilities are moved up by one module to keep the same reach.
+    fn item_vis(&self, vis: &syn::Visibility) -> proc_macro2::TokenStream {
+        self.nested_vis(vis, 1)
+    }
+
+    /// Emits `vis` for an item `depth` modules below the enum's scope, like `item_vis`.
+    fn nested_vis(&self, vis: &syn::Visibility, depth: usize) -> proc_macro2::TokenStream {
+        if self.module.is_none() {
+            return quote! { #vis };
+        }
+        let up = vec![quote! { super }; depth];
+        match vis {
+            syn::Visibility::Public(_) => quote! { #vis },
+            syn::Visibility::Inherited if depth == 1 => quote! { pub(super) },
+            syn::Visibility::Inherited => quote! { pub(in #(#up)::*) },
+            syn::Visibility::Restricted(restricted) => {
+                let path = &restricted.path;
+                let first = path
+                    .segments
+                    .first()
+                    .map(|segment| segment.ident.to_string());
+                match first.as_deref() {
+                    _ if path.leading_colon.is_some() => quote! { #vis },
+                    Some("crate") => quote! { #vis },
+                    Some("self") => {
+                        let rest = path.segments.iter().skip(1);
+                        quote! { pub(in #(#up)::* #(::#rest)*) }
+                    }
+                    _ => quote! { pub(in #(#up)::*::#path) },
+                }
+            }
+        }
+    }
+
+    /// Visibility of the family-wide items (structs, `Plugin`, `Kind`, ...).
+    fn family_vis(&self) -> proc_macro2::TokenStream {
+        self.item_vis(&self.vis)
+    }
+
+    /// Path to a generated item, as seen from next to the enum.
+    fn path(&self, ident: &syn::Ident) -> proc_macro2::TokenStream {
+        match &self.module {
+            Some(module) => quote! { #module::#ident },
+            None => quote! { #ident },
+        }
+    }
+
+    /// Visibility of the bookkeeping fields that only the generated impls touch.
+    fn impl_vis(&self) -> proc_macro2::TokenStream {
+        match &self.module {
+            Some(_) => quote! { pub(super) },
+            None => quote! {},
+        }
+    }
+
+    /// Path to one of the Bevy derives, for the generated structs.
+    fn derive(&self, name: &str) -> proc_macro2::TokenStream {
+        let ident = syn::Ident::new(name, proc_macro2::Span::call_site());
+        let BevyPaths { ecs, deref, .. } = &self.module_bevy;
+        match name {
+            "Message" => quote! { #ecs::message::Message },
+            "Deref" | "DerefMut" => quote! { #deref::#ident },
+            _ => quote! { #ecs::prelude::#ident },
+        }
+    }
+}
+
+/// Field layout of a variant, mirrored by its generated struct.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum VariantStyle {
+    Unit,
+    Tuple,
+    Named,
+}
+
+/// Describes how a variant maps onto its generated struct.
+///
+/// Used by the code that moves payloads between the source enum and the variant structs.
+#[derive(Clone)]
+struct VariantShape {
+    ident: syn::Ident,
+    /// Name of the generated struct, after `rename`/`prefix`/`suffix`.
+    struct_ident: syn::Ident,
+    style: VariantStyle,
+    /// Kind of the generated struct, the derive's unless the variant overrides it.
+    event_kind: EventKind,
+    /// Second kind from `also = ...`: a message that is also an event, or the other way round.
+    also: Option<EventKind>,
+    /// Binding names for the fields: the field names for named variants, `__field{i}` otherwise.
+    bindings: Vec<syn::Ident>,
+    field_types: Vec<syn::Type>,
+    /// Explicit discriminant of the source variant (`A = 3`), carried over to the kind enum.
+    discriminant: Option<syn::Expr>,
+    has_phantom: bool,
+    /// Whether the struct derives `Clone`, which forwarding to `Any` and `Reader::read` need.
+    is_clone: bool,
+    is_debug: bool,
+    /// Whether the struct derives `Reflect`, so the plugin registers it.
+    is_reflect: bool,
+    /// Builder defaults of the fields, from `#[enum_event(default)]`.
+    field_defaults: Vec<Option<syn::Expr>>,
+    /// Left out with `#[enum_event(skip)]`: no struct, only a variant of the kind enum.
+    skip: bool,
+    /// The struct has an `entity` target from `inject_entity` that the variant lacks. An enum
+    /// value has nothing to fill it with, so such structs get no `TryFrom`/`PartialEq` with the
+    /// enum and are handed back by dispatch, `Writer::write` and the conversion to `Any`.
+    injects_entity: bool,
+}
+
+impl VariantShape {
+    fn new(
+        variant: &syn::Variant,
+        struct_ident: syn::Ident,
+        event_kind: EventKind,
+        also: Option<EventKind>,
+        has_phantom: bool,
+        derives: &[syn::Path],
+        field_infos: &[FieldAttrInfo],
+    ) -> Self {
+        let (style, bindings) = match &variant.fields {
+            Fields::Unit => (VariantStyle::Unit, Vec::new()),
+            Fields::Unnamed(fields) => (
+                VariantStyle::Tuple,
+                (0..fields.unnamed.len())
+                    .map(|index| syn::Ident::new(&format!("__field{index}"), variant.ident.span()))
+                    .collect(),
+            ),
+            Fields::Named(fields) => (
+                VariantStyle::Named,
+                fields
+                    .named
+                    .iter()
+                    .map(|field| {
+                        field
+                            .ident
+                            .clone()
+                            .expect("Named fields must have identifiers")
+                    })
+                    .collect(),
+            ),
+        };
+
+        Self {
+            ident: variant.ident.clone(),
+            struct_ident,
+            style,
+            event_kind,
+            also,
+            bindings,
+            field_types: variant
+                .fields
+                .iter()
+                .map(|field| field.ty.clone())
+                .collect(),
+            discriminant: variant.discriminant.as_ref().map(|(_, expr)| expr.clone()),
+            has_phantom,
+            is_clone: derives
+                .iter()
+                .any(|path| path_ends_with_ident(path, "Clone")),
+            is_debug: derives
+                .iter()
+                .any(|path| path_ends_with_ident(path, "Debug")),
+            is_reflect: derives
+                .iter()
+                .any(|path| path_ends_with_ident(path, "Reflect")),
+            field_defaults: field_infos
+                .iter()
+                .map(|info| info.default.clone())
+                .collect(),
+            skip: false,
+            injects_entity: false,
+        }
+    }
+
+    /// Whether the struct is a `Message`.
+    fn is_message(&self) -> bool {
+        self.event_kind == EventKind::Message || self.also == Some(EventKind::Message)
+    }
+
+    /// The observer event kind of the struct, if it is an `Event` or `EntityEvent`.
+    fn observer_kind(&self) -> Option<EventKind> {
+        [self.event_kind]
+            .into_iter()
+            .chain(self.also)
+            .find(|kind| *kind != EventKind::Message)
+    }
+
+    /// `Enum::Variant(__field0, ..)` / `Enum::Variant { a, b }`, usable as pattern and expression.
+    fn enum_tokens(&self, enum_name: &syn::Ident) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        let bindings = &self.bindings;
+        match self.style {
+            VariantStyle::Unit => quote! { #enum_name::#ident },
+            VariantStyle::Tuple => quote! { #enum_name::#ident(#(#bindings),*) },
+            VariantStyle::Named => quote! { #enum_name::#ident { #(#bindings),* } },
+        }
+    }
+
+    /// Pattern that moves every payload field out of the generated struct.
+    fn struct_pattern(&self, layout: &Layout) -> proc_macro2::TokenStream {
+        let path = layout.path(&self.struct_ident);
+        let bindings = &self.bindings;
+        match self.style {
+            VariantStyle::Unit => quote! { #path { .. } },
+            VariantStyle::Tuple => quote! { #path(#(#bindings,)* ..) },
+            VariantStyle::Named => quote! { #path { #(#bindings,)* .. } },
+        }
+    }
+
+    /// Expression that builds the generated struct from the bound payload fields.
+    fn struct_expr(&self, layout: &Layout) -> proc_macro2::TokenStream {
+        let path = layout.path(&self.struct_ident);
+        let bindings = &self.bindings;
+        let phantom = self
+            .has_phantom
+            .then(|| quote! { ::core::marker::PhantomData });
+        match self.style {
+            VariantStyle::Unit if self.has_phantom => {
+                quote! { #path { _phantom: ::core::marker::PhantomData } }
+            }
+            VariantStyle::Unit => quote! { #path },
+            VariantStyle::Tuple => quote! { #path(#(#bindings,)* #phantom) },
+            VariantStyle::Named => {
+                let phantom = phantom.map(|phantom| quote! { _phantom: #phantom });
+                quote! { #path { #(#bindings,)* #phantom } }
+            }
+        }
+    }
+}
+
+/// Generates the hidden `__scope` module, which resolves what the user wrote for the generated
+/// module: aliases of field types, functions for builder defaults, constants for discriminants
+/// and traits bundling the enum's bounds. `extra` holds more of them.
+///
+/// The generated module itself imports nothing, so the user's names can't clash with its items.
+/// `__scope` is the one place that glob-imports the enum's scope: the names a field type uses may
+/// come from the prelude, a glob import or a `use` next to the enum, which the macro can't list
+/// one by one. It holds only `__`-prefixed items, so nothing the glob brings in can clash with
+/// them. Only what names something relative to the enum's scope goes through it, and none of it
+/// is needed in flat mode.
+fn generate_scope(
+    layout: &Layout,
+    generics: &syn::Generics,
+    shapes: &[VariantShape],
+    kind_shapes: &[VariantShape],
+    repr: Option<&syn::Ident>,
+    extra: Vec<proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    if layout.module.is_none() {
+        return quote! {};
+    }
+    let mut items = extra;
+    let mut trait_generics = unbounded(generics);
+    for param in &mut trait_generics.params {
+        match param {
+            syn::GenericParam::Type(param) => {
+                if let Some(default) = param.default.take() {
+                    items
+                        .push(layout.type_alias(&default, format_args!("{}_default", param.ident)));
+                }
+                param.eq_token = None;
+            }
+            syn::GenericParam::Const(param) => {
+                if let Some(default) = param.default.take() {
+                    items.push(layout.hidden_const(
+                        &default,
+                        format_args!("{}_default", param.ident),
+                        &param.ty,
+                    ));
+                }
+                param.eq_token = None;
+            }
+            syn::GenericParam::Lifetime(_) => {}
+        }
+    }
+    let trait_where_clause = &trait_generics.where_clause;
+    let scope_generics = in_scope(generics, ScopeDepth::visit_generics_mut);
+    let (impl_generics, ty_generics, where_clause) = scope_generics.split_for_impl();
+    for (index, predicate) in bound_predicates(generics).iter().enumerate() {
+        if !layout.is_relative_bound(predicate) {
+            continue;
+        }
+        items.push(layout.type_alias(&predicate.bounded_ty, format_args!("bound{index}_type")));
+        let bound = hidden_ident(format_args!("Bound{index}"));
+        let predicate = in_scope(predicate, ScopeDepth::visit_predicate_type_mut);
+        let bounded_ty = &predicate.bounded_ty;
+        let bounds = &predicate.bounds;
+        items.push(quote! {
+            #[allow(non_camel_case_types)]
+            pub trait #bound #trait_generics: #bounds #trait_where_clause {}
+
+            impl #impl_generics #bound #ty_generics for #bounded_ty #where_clause {}
+        });
+    }
+    for shape in shapes {
+        for (index, ty) in shape.field_types.iter().enumerate() {
+            items.push(layout.type_alias(ty, format_args!("{}_{index}", shape.ident)));
+        }
+        for (index, (default, ty)) in shape
+            .field_defaults
+            .iter()
+            .zip(&shape.field_types)
+            .enumerate()
+        {
+            if let Some(default) = default {
+                let name = format!("{}_{index}_default", shape.ident);
+                items.push(layout.hidden_fn(default, name, ty));
+            }
+        }
+    }
+    let repr: syn::Type = match repr {
+        Some(repr) => syn::parse_quote! { #repr },
+        None => syn::parse_quote! { isize },
+    };
+    for shape in kind_shapes {
+        if let Some(discriminant) = &shape.discriminant {
+            let name = format!("{}_discriminant", shape.ident);
+            items.push(layout.hidden_const(discriminant, name, &repr));
+        }
+    }
+
+    items.retain(|item| !item.is_empty());
+    if items.is_empty() {
+        return quote! {};
+    }
+    quote! {
+        #[doc(hidden)]
+        pub mod __scope {
+            #[allow(unused_imports)]
+            use super::super::*;
+
+            #(#items)*
+        }
+    }
+}
+
+/// Generates `From`, `TryFrom` and `PartialEq` impls between the source enum and each variant struct.
+///
+/// These are emitted next to the generated module rather than inside it, so that enums declared
+/// inside function bodies can still be named.
+fn generate_conversions(
+    enum_name: &syn::Ident,
+    layout: &Layout,
+    generics: &syn::Generics,
+    shapes: &[VariantShape],
+) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let impls = shapes.iter().map(|shape| {
+        let struct_path = layout.path(&shape.struct_ident);
+        let struct_ty = quote! { #struct_path #ty_generics };
+        let enum_tokens = shape.enum_tokens(enum_name);
+        let struct_pattern = shape.struct_pattern(layout);
+        let struct_expr = shape.struct_expr(layout);
+
+        // Payloads are only comparable when every field type is. The higher-ranked bound keeps
+        // concrete, non-comparable field types from turning into a hard error at the impl site;
+        // the impl simply does not apply to them.
+        let mut eq_generics = generics.clone();
+        let eq_predicates = &mut eq_generics.make_where_clause().predicates;
+        for ty in &shape.field_types {
+            eq_predicates.push(syn::parse_quote! { for<'__enum_event> #ty: ::core::cmp::PartialEq });
+        }
+        let eq_impls = !shape.injects_entity;
+        let eq_where_clause = &eq_generics.where_clause;
+        let eq_body = if shape.bindings.is_empty() {
+            quote! { true }
+        } else {
+            let comparisons = shape.bindings.iter().enumerate().map(|(index, binding)| {
+                let member = match shape.style {
+                    VariantStyle::Named => quote! { #binding },
+                    _ => {
+                        let index = syn::Index::from(index);
+                        quote! { #index }
+                    }
+                };
+                quote! { ::core::cmp::PartialEq::eq(#binding, &__rhs.#member) }
+            });
+            quote! { #(#comparisons)&&* }
+        };
+
+        let eq_impls = eq_impls.then(|| quote! {
+            impl #impl_generics ::core::cmp::PartialEq<#struct_ty> for #enum_name #ty_generics #eq_where_clause {
+                #[allow(unreachable_patterns)]
+                fn eq(&self, __rhs: &#struct_ty) -> bool {
+                    match self {
+                        #enum_tokens => #eq_body,
+                        _ => false,
+                    }
+                }
+            }
+
+            impl #impl_generics ::core::cmp::PartialEq<#enum_name #ty_generics> for #struct_ty #eq_where_clause {
+                #[inline]
+                fn eq(&self, other: &#enum_name #ty_generics) -> bool {
+                    other == self
+                }
+            }
+        });
+
+        let try_from = (!shape.injects_entity).then(|| quote! {
+            impl #impl_generics ::core::convert::TryFrom<#enum_name #ty_generics> for #struct_ty #where_clause {
+                /// The original value, returned unchanged when it holds a different variant.
+                type Error = #enum_name #ty_generics;
+
+                #[inline]
+                #[allow(unreachable_patterns)]
+                fn try_from(value: #enum_name #ty_generics) -> ::core::result::Result<Self, Self::Error> {
+                    match value {
+                        #enum_tokens => ::core::result::Result::Ok(#struct_expr),
+                        other => ::core::result::Result::Err(other),
+                    }
+                }
+            }
+        });
+
+        quote! {
+            impl #impl_generics ::core::convert::From<#struct_ty> for #enum_name #ty_generics #where_clause {
+                #[inline]
+                fn from(value: #struct_ty) -> Self {
+                    let #struct_pattern = value;
+                    #enum_tokens
+                }
+            }
+
+            #try_from
+            #eq_impls
+        }
+    });
+
+    quote! { #(#impls)* }
+}
+
+/// Name of the builder generated for `shape`, if it gets one.
+///
+/// Only named variants get a builder, and a field called `build` would clash with the method
+/// that finishes it.
+fn builder_ident(shape: &VariantShape) -> Option<syn::Ident> {
+    let is_named = shape.style == VariantStyle::Named
+        || (shape.style == VariantStyle::Unit && shape.injects_entity);
+    let has_builder = is_named && !shape.bindings.iter().any(|binding| binding == "build");
+    has_builder.then(|| {
+        syn::Ident::new(
+            &format!("{}Builder", shape.struct_ident),
+            shape.struct_ident.span(),
+        )
+    })
+}
+
+/// Generates `new`, `into_parts`/`from_parts` and the builder of a variant struct.
+///
+/// Emitted inside the generated module. The builder tracks its required fields in type
+/// parameters, `()` while unset and the field type once set, so `build` only exists once every
+/// required field is set. Fields with `#[enum_event(default)]` start out with their default.
+/// An `entity` injected by `inject_entity` comes first everywhere, so callers always spell out
+/// the target.
+fn generate_constructors(
+    shape: &VariantShape,
+    layout: &Layout,
+    generics: &syn::Generics,
+    struct_vis: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let generics = &layout.module_generics(generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let struct_ident = &shape.struct_ident;
+    let payload = &shape.bindings;
+    let injected = shape
+        .injects_entity
+        .then(|| syn::Ident::new("entity", struct_ident.span()));
+    let mut bindings = shape.bindings.clone();
+    let mut field_types: Vec<_> = shape
+        .field_types
+        .iter()
+        .enumerate()
+        .map(|(index, ty)| layout.field_type(&shape.ident, index, ty))
+        .collect();
+    let mut field_defaults: Vec<_> = shape
+        .field_defaults
+        .iter()
+        .enumerate()
+        .map(|(index, default)| {
+            default.as_ref().map(|expr| {
+                layout.module_expr(expr, format_args!("{}_{index}_default", shape.ident))
+            })
+        })
+        .collect();
+    if let Some(entity) = &injected {
+        let ecs = &layout.module_bevy.ecs;
+        bindings.insert(0, entity.clone());
+        field_types.insert(0, syn::parse_quote! { #ecs::prelude::Entity });
+        field_defaults.insert(0, None);
+    }
+    let bindings = &bindings;
+    let field_types = &field_types;
+    let args = bindings
+        .iter()
+        .zip(field_types)
+        .map(|(binding, ty)| quote! { #binding: #ty });
+    let injected = injected.map(|entity| quote! { #entity, });
+    let phantom = shape
+        .has_phantom
+        .then(|| quote! { ::core::marker::PhantomData });
+    let (struct_expr, struct_pattern) = match shape.style {
+        VariantStyle::Unit if injected.is_some() => {
+            let phantom = phantom.map(|phantom| quote! { _phantom: #phantom });
+            (
+                quote! { Self { #injected #phantom } },
+                quote! { Self { #injected .. } },
+            )
+        }
+        VariantStyle::Unit => match phantom {
+            Some(phantom) => (
+                quote! { Self { _phantom: #phantom } },
+                quote! { Self { .. } },
+            ),
+            None => (quote! { Self }, quote! { Self }),
+        },
+        // The injected target follows the payload, so the positions match the variant's
+        VariantStyle::Tuple => (
+            quote! { Self(#(#payload,)* #injected #phantom) },
+            quote! { Self(#(#payload,)* #injected ..) },
+        ),
+        VariantStyle::Named => {
+            let phantom = phantom.map(|phantom| quote! { _phantom: #phantom });
+            (
+                quote! { Self { #injected #(#payload,)* #phantom } },
+                quote! { Self { #injected #(#payload,)* .. } },
+            )
+        }
+    };
+
+    // Unit structs split into `()`, which is spelled out as neither type nor value
+    let into_parts = if bindings.is_empty() {
+        quote! {
+            pub fn into_parts(self) {}
+        }
+    } else {
+        quote! {
+            pub fn into_parts(self) -> (#(#field_types,)*) {
+                let #struct_pattern = self;
+                (#(#bindings,)*)
+            }
+        }
+    };
+    let parts = quote! {
+        /// Splits the value into its fields.
+        #[inline]
+        #into_parts
+
+        /// Builds the value from its fields, in declaration order.
+        #[inline]
+        pub fn from_parts((#(#bindings,)*): (#(#field_types,)*)) -> Self {
+            #struct_expr
+        }
+    };
+
+    let builder = builder_ident(shape).map(|builder| {
+        let marker = family_marker(generics);
+        let args = generic_args(generics);
+        // Defaults of the struct's parameters can't come before the state parameters
+        let mut builder_generics = generics.clone();
+        for param in &mut builder_generics.params {
+            match param {
+                syn::GenericParam::Type(param) => {
+                    param.eq_token = None;
+                    param.default = None;
+                }
+                syn::GenericParam::Const(param) => {
+                    param.eq_token = None;
+                    param.default = None;
+                }
+                syn::GenericParam::Lifetime(_) => {}
+            }
+        }
+        let fields: Vec<_> = bindings
+            .iter()
+            .zip(field_types)
+            .zip(&field_defaults)
+            .collect();
+        let states: Vec<_> = fields
+            .iter()
+            .filter(|(_, default)| default.is_none())
+            .enumerate()
+            .map(|(index, ((binding, _), _))| {
+                (
+                    *binding,
+                    syn::Ident::new(&format!("__F{index}"), binding.span()),
+                )
+            })
+            .collect();
+        let state_of = |binding: &syn::Ident| {
+            states
+                .iter()
+                .find(|(required, _)| *required == binding)
+                .map(|(_, state)| state)
+        };
+        let state_params: Vec<_> = states.iter().map(|(_, state)| state).collect();
+        for state in &state_params {
+            builder_generics.params.push(syn::parse_quote! { #state });
+        }
+        let (builder_impl_generics, _, builder_where_clause) = builder_generics.split_for_impl();
+        let builder_fields = fields.iter().map(|((binding, ty), _)| match state_of(binding) {
+            Some(state) => quote! { #binding: #state },
+            None => quote! { #binding: #ty },
+        });
+        let unset = states.iter().map(|_| quote! { () });
+        let initial_values = fields.iter().map(|((binding, _), default)| match default {
+            Some(default) => quote! { #binding: #default },
+            None => quote! { #binding: () },
+        });
+        let setters = fields.iter().map(|((binding, ty), _)| {
+            let doc = format!("Sets `{binding}`.");
+            match state_of(binding) {
+                Some(_) => {
+                    let states = states.iter().map(|(required, state)| {
+                        if required == binding {
+                            quote! { #ty }
+                        } else {
+                            quote! { #state }
+                        }
+                    });
+                    let values = bindings.iter().map(|other| {
+                        if other == *binding {
+                            quote! { #other }
+                        } else {
+                            quote! { #other: self.#other }
+                        }
+                    });
+                    quote! {
+                        #[doc = #doc]
+                        #[inline]
+                        pub fn #binding(self, #binding: #ty) -> #builder<#(#args,)* #(#states),*> {
+                            #builder {
+                                #(#values,)*
+                                __marker: ::core::marker::PhantomData,
+                            }
+                        }
+                    }
+                }
+                None => quote! {
+                    #[doc = #doc]
+                    #[inline]
+                    pub fn #binding(mut self, #binding: #ty) -> Self {
+                        self.#binding = #binding;
+                        self
+                    }
+                },
+            }
+        });
+        let required_types = fields
+            .iter()
+            .filter(|(_, default)| default.is_none())
+            .map(|((_, ty), _)| ty);
+        let builder_doc = format!(
+            "Builder for [`{struct_ident}`], created by [`{struct_ident}::builder`].\n\n`build` becomes available once every field without a default is set."
+        );
+
+        quote! {
+            #[doc = #builder_doc]
+            #[must_use]
+            #struct_vis struct #builder #builder_generics #builder_where_clause {
+                #(#builder_fields,)*
+                __marker: #marker,
+            }
+
+            impl #impl_generics #struct_ident #ty_generics #where_clause {
+                /// Starts a builder; fields with a default may be left unset.
+                #[inline]
+                pub fn builder() -> #builder<#(#args,)* #(#unset),*> {
+                    #builder {
+                        #(#initial_values,)*
+                        __marker: ::core::marker::PhantomData,
+                    }
+                }
+            }
+
+            impl #builder_impl_generics #builder<#(#args,)* #(#state_params),*> #builder_where_clause {
+                #(#setters)*
+            }
+
+            impl #impl_generics #builder<#(#args,)* #(#required_types),*> #where_clause {
+                /// Builds the value.
+                #[inline]
+                pub fn build(self) -> #struct_ident #ty_generics {
+                    #struct_ident::new(#(self.#bindings),*)
+                }
+            }
+        }
+    });
+
+    quote! {
+        impl #impl_generics #struct_ident #ty_generics #where_clause {
+            /// Creates the value from its fields, in declaration order.
+            #[inline]
+            pub const fn new(#(#args),*) -> Self {
+                #struct_expr
+            }
+
+            #parts
+        }
+
+        #builder
+    }
+}
+
+/// Generates the `VariantOf` impl of each variant struct.
+///
+/// Takes every variant, skipped ones included, so `INDEX` matches the position in `Kind::ALL`.
+fn generate_variant_of(
+    enum_name: &syn::Ident,
+    layout: &Layout,
+    generics: &syn::Generics,
+    kind_shapes: &[VariantShape],
+) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let family = enum_name.to_string();
+
+    let impls = kind_shapes
+        .iter()
+        .enumerate()
+        .filter(|(_, shape)| !shape.skip)
+        .map(|(index, shape)| {
+            let struct_path = layout.path(&shape.struct_ident);
+            let name = shape.ident.to_string();
+            quote! {
+                impl #impl_generics ::bevy_enum_event::VariantOf<#enum_name #ty_generics>
+                    for #struct_path #ty_generics #where_clause
+                {
+                    const NAME: &'static str = #name;
+                    const INDEX: usize = #index;
+                    const FAMILY: &'static str = #family;
+
+                    #[inline]
+                    fn into_family(self) -> #enum_name #ty_generics {
+                        #enum_name::from(self)
+                    }
+                }
+            }
+        });
+
+    quote! { #(#impls)* }
+}
+
+/// Generates the `EnumFamily` impl of the source enum.
+///
+/// Names and count cover every variant like `Kind`, `Variants` only the generated structs.
+fn generate_enum_family(
+    enum_name: &syn::Ident,
+    layout: &Layout,
+    generics: &syn::Generics,
+    kind_shapes: &[VariantShape],
+    event_kind: EventKind,
+) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let module_path = match &layout.module {
+        Some(module) => {
+            let module = module.to_string();
+            quote! { ::core::concat!(::core::module_path!(), "::", #module) }
+        }
+        None => quote! { ::core::module_path!() },
+    };
+    let kind = match event_kind {
+        EventKind::Event => quote! { Event },
+        EventKind::Message => quote! { Message },
+        EventKind::EntityEvent => quote! { EntityEvent },
+    };
+
+    let struct_paths = kind_shapes
+        .iter()
+        .filter(|shape| !shape.skip)
+        .map(|shape| layout.path(&shape.struct_ident));
+    let variant_names = kind_shapes.iter().map(|shape| shape.ident.to_string());
+    let variant_count = kind_shapes.len();
+
+    quote! {
+        impl #impl_generics ::bevy_enum_event::EnumFamily for #enum_name #ty_generics #where_clause {
+            const VARIANT_NAMES: &'static [&'static str] = &[#(#variant_names),*];
+            const VARIANT_COUNT: usize = #variant_count;
+            const MODULE_PATH: &'static str = #module_path;
+            const EVENT_KIND: ::bevy_enum_event::EventKind = ::bevy_enum_event::EventKind::#kind;
+
+            type Variants = (#(#struct_paths #ty_generics,)*);
+        }
+    }
+}
+
+/// Generates the dispatch methods on the source enum and a `Command` impl for each variant struct.
+///
+/// Event variants get `trigger`/`trigger_world`, message variants get `write`/`write_world`.
+/// Each moves the payload into the matching variant struct before handing it to Bevy. When some
+/// variants are skipped or of the other kind they return `Result<(), Self>`, handing those values
+/// back unchanged.
+fn generate_dispatch(
+    enum_name: &syn::Ident,
+    layout: &Layout,
+    generics: &syn::Generics,
+    shapes: &[VariantShape],
+    event_kind: EventKind,
+    has_skipped: bool,
+) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ecs = &layout.bevy.ecs;
+    let struct_tys: Vec<_> = shapes
+        .iter()
+        .map(|shape| {
+            let struct_path = layout.path(&shape.struct_ident);
+            quote! { #struct_path #ty_generics }
+        })
+        .collect();
+
+    // Bevy only implements `Event`/`Message` for `Send + Sync + 'static` types, which can't be
+    // assumed for generic enums.
+    let mut dispatch_generics = generics.clone();
+    if !generics.params.is_empty() {
+        let predicates = &mut dispatch_generics.make_where_clause().predicates;
+        for struct_ty in &struct_tys {
+            predicates.push(syn::parse_quote! { #struct_ty: ::core::marker::Send + ::core::marker::Sync + 'static });
+        }
+    }
+    let dispatch_where_clause = &dispatch_generics.where_clause;
+
+    let command_impls = shapes.iter().zip(&struct_tys).map(|(shape, struct_ty)| {
+        let command_apply = match shape.event_kind {
+            EventKind::Message => quote! { world.write_message(self); },
+            EventKind::Event | EventKind::EntityEvent => quote! { world.trigger(self); },
+        };
+        quote! {
+            impl #impl_generics #ecs::system::Command for #struct_ty #dispatch_where_clause {
+                fn apply(self, world: &mut #ecs::world::World) {
+                    #command_apply
+                }
+            }
+        }
+    });
+
+    // Message variants are written, event variants triggered. A family without variants of a
+    // kind gets no methods for it, unless it is the derive's own kind.
+    let groups = [
+        (EventKind::Message, event_kind == EventKind::Message),
+        (EventKind::Event, event_kind != EventKind::Message),
+    ];
+    let methods = groups.into_iter().map(|(group, is_default)| {
+        let is_message = group == EventKind::Message;
+        let in_group = |shape: &VariantShape| {
+            if is_message {
+                shape.is_message()
+            } else {
+                shape.observer_kind().is_some()
+            }
+        };
+        let (handled, has_unhandled) = handled_shapes(shapes, has_skipped, |shape| {
+            in_group(shape) && !shape.injects_entity
+        });
+        let has_other_kind = !shapes.iter().all(in_group);
+        let has_injected = shapes
+            .iter()
+            .any(|shape| in_group(shape) && shape.injects_entity);
+        let shapes = handled;
+        if shapes.is_empty() && !is_default {
+            return quote! {};
+        }
+
+        let (commands_fn, world_fn, commands_call, world_call) = if is_message {
+            (
+                quote! { write },
+                quote! { write_world },
+                quote! { commands.write_message },
+                quote! { world.write_message },
+            )
+        } else {
+            (
+                quote! { trigger },
+                quote! { trigger_world },
+                quote! { commands.trigger },
+                quote! { world.trigger },
+            )
+        };
+        let (commands_doc, world_doc) = if is_message {
+            (
+                "Writes this value as the matching variant message through `commands`.",
+                "Writes this value as the matching variant message directly into `world`.",
+            )
+        } else {
+            (
+                "Triggers this value as the matching variant event through `commands`.",
+                "Triggers this value as the matching variant event directly on `world`.",
+            )
+        };
+        let (output, ok, skipped_arm, skipped_doc) = if has_unhandled {
+            let skipped_doc = match (has_other_kind, is_message) {
+                (false, _) => "Returns the value unchanged if its variant is skipped.",
+                (true, true) => "Returns the value unchanged if its variant is skipped or not a message.",
+                (true, false) => "Returns the value unchanged if its variant is skipped or not an event.",
+            };
+            let injected_doc = has_injected.then(|| quote! {
+                ///
+                /// Variants whose struct gets an injected `entity` are handed back too; build
+                /// the struct with `new` to give it a target.
+            });
+            (
+                quote! { -> ::core::result::Result<(), Self> },
+                quote! { ::core::result::Result::Ok(()) },
+                quote! { other => ::core::result::Result::Err(other), },
+                quote! {
+                    ///
+                    /// # Errors
+                    ///
+                    #[doc = #skipped_doc]
+                    #injected_doc
+                    #[allow(unreachable_patterns)]
+                },
+            )
+        } else {
+            (quote! {}, quote! {}, quote! {}, quote! {})
+        };
+
+        let enum_patterns: Vec<_> = shapes
+            .iter()
+            .map(|shape| shape.enum_tokens(enum_name))
+            .collect();
+        let struct_exprs: Vec<_> = shapes
+            .iter()
+            .map(|shape| shape.struct_expr(layout))
+            .collect();
+
+        quote! {
+            #[doc = #commands_doc]
+            #skipped_doc
+            pub fn #commands_fn(self, commands: &mut #ecs::system::Commands<'_, '_>) #output #dispatch_where_clause {
+                match self {
+                    #(#enum_patterns => { #commands_call(#struct_exprs); #ok })*
+                    #skipped_arm
+                }
+            }
+
+            #[doc = #world_doc]
+            #skipped_doc
+            pub fn #world_fn(self, world: &mut #ecs::world::World) #output #dispatch_where_clause {
+                match self {
+                    #(#enum_patterns => { #world_call(#struct_exprs); #ok })*
+                    #skipped_arm
+                }
+            }
+        }
+    });
+
+    quote! {
+        impl #impl_generics #enum_name #ty_generics #where_clause {
+            #(#methods)*
+        }
+
+        #(#command_impls)*
+    }
+}
+
+/// The generic parameters as arguments, for naming a generated type with extra parameters.
+fn generic_args(generics: &syn::Generics) -> Vec<proc_macro2::TokenStream> {
+    generics
+        .params
+        .iter()
+        .map(|param| match param {
+            syn::GenericParam::Type(param) => {
+                let ident = &param.ident;
+                quote! { #ident }
+            }
+            syn::GenericParam::Lifetime(param) => {
+                let lifetime = &param.lifetime;
+                quote! { #lifetime }
+            }
+            syn::GenericParam::Const(param) => {
+                let ident = &param.ident;
+                quote! { #ident }
+            }
+        })
+        .collect()
+}
+
+/// `PhantomData` marker that ties a generated helper type to one instantiation of a generic family.
+fn family_marker(generics: &syn::Generics) -> proc_macro2::TokenStream {
+    let entries = generics
+        .type_params()
+        .map(|param| {
+            let ident = &param.ident;
+            quote! { #ident }
+        })
+        .chain(generics.lifetimes().map(|param| {
+            let lifetime = &param.lifetime;
+            quote! { &#lifetime () }
+        }));
+    quote! { ::core::marker::PhantomData<fn() -> (#(#entries,)*)> }
+}
+
+/// Whether the family `Reader`/`Writer` system params are generated.
+///
+/// They cover the message variants. System params already carry the `'w`/`'s` lifetimes, and
+/// messages must be `'static`, so families with lifetime parameters are left out.
+fn has_family_io(generics: &syn::Generics, shapes: &[VariantShape]) -> bool {
+    shapes.iter().any(VariantShape::is_message) && generics.lifetimes().next().is_none()
+}
+
+/// The shapes of the variants handled by `filter`, and whether any generated or skipped variant
+/// is left out, in which case the generated code hands such values back as an error.
+fn handled_shapes(
+    shapes: &[VariantShape],
+    has_skipped: bool,
+    filter: impl Fn(&VariantShape) -> bool,
+) -> (Vec<VariantShape>, bool) {
+    let handled: Vec<_> = shapes
+        .iter()
+        .filter(|shape| filter(shape))
+        .cloned()
+        .collect();
+    let has_unhandled = has_skipped || handled.len() < shapes.len();
+    (handled, has_unhandled)
+}
+
+/// Generates the `Plugin` type that performs the per-variant `App` setup of a family.
+///
+/// Nothing is generated when `bevy_app` isn't available, e.g. with `crate = bevy_ecs`.
+///
+/// Emitted inside the generated module. It registers every message variant struct with
+/// `add_message` and adds the observers that forward each event variant to the family-wide
+/// `Any` event.
+fn generate_plugin(
+    layout: &Layout,
+    generics: &syn::Generics,
+    shapes: &[VariantShape],
+) -> proc_macro2::TokenStream {
+    let BevyPaths {
+        ecs,
+        app: Some(app),
+        reflect,
+        ..
+    } = &layout.module_bevy
+    else {
+        return quote! {};
+    };
+    let generics = &layout.module_generics(generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let Layout {
+        plugin,
+        any,
+        write_order,
+        ..
+    } = layout;
+    let family_vis = layout.family_vis();
+    let struct_tys: Vec<_> = shapes
+        .iter()
+        .map(|shape| {
+            let struct_ident = &shape.struct_ident;
+            quote! { #struct_ident #ty_generics }
+        })
+        .collect();
+
+    let mut bounded_tys = struct_tys.clone();
+    let has_messages = shapes.iter().any(VariantShape::is_message);
+    let has_events = shapes.iter().any(|shape| shape.observer_kind().is_some());
+    let mut registrations: Vec<_> = shapes
+        .iter()
+        .zip(&struct_tys)
+        .filter(|(shape, _)| shape.is_message())
+        .map(|(_, struct_ty)| quote! { app.add_message::<#struct_ty>(); })
+        .collect();
+    // Generic structs can only be registered where `GetTypeRegistration` can be named
+    let reflected: Vec<_> = shapes
+        .iter()
+        .zip(&struct_tys)
+        .filter(|(shape, _)| shape.is_reflect && (generics.params.is_empty() || reflect.is_some()))
+        .map(|(_, struct_ty)| struct_ty)
+        .collect();
+    registrations.extend(
+        reflected
+            .iter()
+            .map(|struct_ty| quote! { app.register_type::<#struct_ty>(); }),
+    );
+    if has_family_io(generics, shapes) {
+        bounded_tys.push(quote! { #write_order #ty_generics });
+        registrations.push(quote! { app.add_message::<#write_order #ty_generics>(); });
+    }
+    if has_events {
+        bounded_tys.push(quote! { #any #ty_generics });
+    }
+    // Forwarding copies the payload out of `On`, so variants without `Clone` are left out
+    registrations.extend(
+        shapes
+            .iter()
+            .zip(&struct_tys)
+            .filter(|(shape, _)| shape.observer_kind().is_some() && shape.is_clone)
+            .map(|(shape, struct_ty)| {
+                let variant_ident = &shape.ident;
+                quote! {
+                    {
+                        app.add_observer(
+                            |event: #ecs::observer::On<#struct_ty>,
+                             mut commands: #ecs::system::Commands| {
+                                commands.trigger(#any::#variant_ident(
+                                    ::core::clone::Clone::clone(event.event()),
+                                ));
+                            },
+                        );
+                    }
+                }
+            }),
+    );
+
+    let plugin_doc = match (has_messages, has_events) {
+        (true, false) => {
+            "Plugin that registers every message type of this family with `add_message`.".to_string()
+        }
+        (false, _) => format!(
+            "Plugin that forwards every event of this family to [`{any}`], so one observer can handle the whole family."
+        ),
+        (true, true) => format!(
+            "Plugin that registers every message type of this family with `add_message` and forwards every event to [`{any}`]."
+        ),
+    };
+
+    let plugin_doc = if reflected.is_empty() {
+        plugin_doc
+    } else {
+        format!("{plugin_doc}\n\nAlso registers the structs that derive `Reflect` with the type registry.")
+    };
+
+    if generics.params.is_empty() {
+        return quote! {
+            #[doc = #plugin_doc]
+            #[derive(Clone, Copy, Debug, Default)]
+            #family_vis struct #plugin;
+
+            impl #app::Plugin for #plugin {
+                fn build(&self, app: &mut #app::App) {
+                    #(#registrations)*
+                }
+            }
+        };
+    }
+
+    // Generic families need a marker so the plugin is tied to one instantiation.
+    let marker = family_marker(generics);
+    let mut plugin_generics = generics.clone();
+    let predicates = &mut plugin_generics.make_where_clause().predicates;
+    predicates.push(syn::parse_quote! { Self: 'static });
+    for struct_ty in &bounded_tys {
+        predicates.push(
+            syn::parse_quote! { #struct_ty: ::core::marker::Send + ::core::marker::Sync + 'static },
+        );
+    }
+    for (shape, struct_ty) in shapes.iter().zip(&struct_tys) {
+        if shape.observer_kind().is_some() && shape.is_clone {
+            predicates.push(syn::parse_quote! { #struct_ty: ::core::clone::Clone });
+        }
+    }
+    if let Some(reflect) = reflect {
+        for struct_ty in &reflected {
+            predicates.push(syn::parse_quote! { #struct_ty: #reflect::GetTypeRegistration });
+        }
+    }
+    let plugin_where_clause = &plugin_generics.where_clause;
+
+    quote! {
+        #[doc = #plugin_doc]
+        #family_vis struct #plugin #impl_generics (#marker) #where_clause;
+
+        impl #impl_generics ::core::default::Default for #plugin #ty_generics #where_clause {
+            fn default() -> Self {
+                Self(::core::marker::PhantomData)
+            }
+        }
+
+        impl #impl_generics #app::Plugin for #plugin #ty_generics #plugin_where_clause {
+            fn build(&self, app: &mut #app::App) {
+                #(#registrations)*
+            }
+        }
+    }
+}
+
+/// Generates the family `Reader` and `Writer` system params of an `EnumMessage`.
+///
+/// Returns the items that go inside the generated module (the param structs and the
+/// `WriteOrder` bookkeeping message) and the inherent impls, which are emitted next to
+/// the module because they need to name the source enum.
+fn generate_family_io(
+    enum_name: &syn::Ident,
+    layout: &Layout,
+    generics: &syn::Generics,
+    shapes: &[VariantShape],
+    has_skipped: bool,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    if !has_family_io(generics, shapes) {
+        return (quote! {}, quote! {});
+    }
+    // Variants of the event kinds are handed back by `Writer::write` like skipped ones
+    let (shapes, has_skipped) = handled_shapes(shapes, has_skipped, VariantShape::is_message);
+    let shapes = &shapes[..];
+
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let Layout {
+        plugin,
+        reader,
+        writer,
+        write_order,
+        ..
+    } = layout;
+    let ecs = &layout.bevy.ecs;
+    let impl_vis = layout.impl_vis();
+    let family_vis = layout.family_vis();
+    let message_derive = layout.derive("Message");
+    // In module mode the params go into the nested `__io` module, where the `SystemParam`
+    // derive's unqualified `Result` can be imported without reserving the name
+    let io = layout.module.as_ref().map(|_| quote! { __io });
+    let local = |ident: &syn::Ident| match &io {
+        Some(_) => quote! { super::#ident },
+        None => quote! { #ident },
+    };
+    let (io_vis, io_field_vis, io_ecs) = match &io {
+        Some(_) => (
+            layout.nested_vis(&layout.vis, 2),
+            layout.nested_vis(&syn::Visibility::Inherited, 2),
+            &layout.io_bevy.ecs,
+        ),
+        None => (
+            layout.family_vis(),
+            layout.impl_vis(),
+            &layout.module_bevy.ecs,
+        ),
+    };
+    let module_generics = layout.module_generics(generics);
+    let module_params: Vec<_> = module_generics.params.iter().collect();
+    let marker = family_marker(generics);
+    let write_order_ty = local(write_order);
+    let mut param_generics = module_generics.clone();
+    for shape in shapes {
+        let struct_ty = local(&shape.struct_ident);
+        param_generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote! { #struct_ty #ty_generics: #io_ecs::message::Message });
+    }
+    param_generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote! { #write_order_ty #ty_generics: #io_ecs::message::Message });
+    let param_where_clause = &param_generics.where_clause;
+    let (_, _, where_clause) = module_generics.split_for_impl();
+
+    let fields: Vec<_> = (0..shapes.len())
+        .map(|index| syn::Ident::new(&format!("variant_{index}"), proc_macro2::Span::call_site()))
+        .collect();
+    let indices: Vec<_> = (0..shapes.len()).collect();
+    let struct_tys: Vec<_> = shapes
+        .iter()
+        .map(|shape| {
+            let struct_ty = local(&shape.struct_ident);
+            quote! { #struct_ty #ty_generics }
+        })
+        .collect();
+
+    let write_order_doc = format!(
+        "Records the variant and message id of every write through [`{writer}`], so [`{reader}`] can restore the global write order."
+    );
+    // Without `bevy_app` there is no plugin to register the bookkeeping message, so it is shown
+    // for the caller to register alongside the variants
+    let (requires, write_order_doc, write_order_hidden) = if layout.bevy.app.is_some() {
+        (
+            format!("Requires [`{plugin}`]."),
+            write_order_doc,
+            Some(quote! { #[doc(hidden)] }),
+        )
+    } else {
+        let requires = format!(
+            "Requires a `Messages` resource for every variant message and for [`{write_order}`], for example from `MessageRegistry::register_message`."
+        );
+        let write_order_doc = format!(
+            "{write_order_doc}\n\nRegister it like the variant messages, since there is no generated plugin to do it."
+        );
+        (requires, write_order_doc, None)
+    };
+    let reader_doc = format!(
+        "Reads the messages of every variant of this family as source enum values.\n\n{requires}"
+    );
+    let writer_doc = format!(
+        "Writes source enum values into the matching variant message buffers, recording the global write order for [`{reader}::read_in_write_order`].\n\n{requires}"
+    );
+
+    let params_defs = quote! {
+        #[doc = #reader_doc]
+        #[derive(#io_ecs::system::SystemParam)]
+        #io_vis struct #reader<'w, 's, #(#module_params),*> #param_where_clause {
+            #(#io_field_vis #fields: #io_ecs::message::MessageReader<'w, 's, #struct_tys>,)*
+            #io_field_vis order: #io_ecs::message::MessageReader<'w, 's, #write_order_ty #ty_generics>,
+        }
+
+        #[doc = #writer_doc]
+        #[derive(#io_ecs::system::SystemParam)]
+        #io_vis struct #writer<'w, #(#module_params),*> #param_where_clause {
+            #(#io_field_vis #fields: #io_ecs::message::MessageWriter<'w, #struct_tys>,)*
+            #io_field_vis order: #io_ecs::message::MessageWriter<'w, #write_order_ty #ty_generics>,
+        }
+    };
+    // The params name nothing of the generated module but its items, its hidden `__scope` and
+    // the `bevy_ecs` import for Bevy's derives, so `__io` imports just those
+    let scope_import = layout
+        .generics_use_scope(generics)
+        .then(|| quote! { use super::__scope; });
+    let ecs_import = layout
+        .needs_ecs_import()
+        .then(|| quote! { #[allow(unused_imports)] use super::bevy_ecs; });
+    let params_defs = match &io {
+        Some(io) => quote! {
+            mod #io {
+                #scope_import
+                #ecs_import
+                use ::core::result::Result;
+
+                #params_defs
+            }
+
+            #family_vis use self::#io::{#reader, #writer};
+        },
+        None => params_defs,
+    };
+
+    let inner = quote! {
+        #[doc = #write_order_doc]
+        #write_order_hidden
+        #[derive(#message_derive)]
+        #family_vis struct #write_order #module_generics (#impl_vis usize, #impl_vis usize, #impl_vis #marker) #where_clause;
+
+        #params_defs
+    };
+
+    let mut impl_generics = generics.clone();
+    let mut read_generics = generics.clone();
+    for shape in shapes {
+        let struct_path = layout.path(&shape.struct_ident);
+        impl_generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote! { #struct_path #ty_generics: #ecs::message::Message });
+        read_generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote! { for<'__enum_event> #struct_path #ty_generics: ::core::clone::Clone });
+    }
+    let write_order_path = layout.path(write_order);
+    let reader_path = layout.path(reader);
+    let writer_path = layout.path(writer);
+    impl_generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote! { #write_order_path #ty_generics: #ecs::message::Message });
+    let impl_where_clause = &impl_generics.where_clause;
+    let read_where_clause = &read_generics.where_clause;
+    let writable: Vec<_> = (0..shapes.len())
+        .filter(|index| !shapes[*index].injects_entity)
+        .collect();
+    let enum_patterns = writable
+        .iter()
+        .map(|index| shapes[*index].enum_tokens(enum_name));
+    let struct_exprs = writable
+        .iter()
+        .map(|index| shapes[*index].struct_expr(layout));
+    let write_fields = writable.iter().map(|index| &fields[*index]);
+    // The params' own lifetimes go in front; `split_for_impl` drops the enum's defaults
+    let mut reader_generics = generics.clone();
+    reader_generics.params.insert(0, syn::parse_quote! { 's });
+    reader_generics.params.insert(0, syn::parse_quote! { 'w });
+    let (reader_impl_generics, _, _) = reader_generics.split_for_impl();
+    let mut writer_generics = generics.clone();
+    writer_generics.params.insert(0, syn::parse_quote! { 'w });
+    let (writer_impl_generics, _, _) = writer_generics.split_for_impl();
+    let param_args = generic_args(generics);
+    let write = if has_skipped || writable.len() < shapes.len() {
+        quote! {
+            /// Writes `message` into the buffer of its variant.
+            ///
+            /// # Errors
+            ///
+            /// Returns `message` unchanged if its variant is skipped, not a message or gets an
+            /// injected `entity`.
+            #[allow(unreachable_patterns)]
+            pub fn write(&mut self, message: #enum_name #ty_generics) -> ::core::result::Result<(), #enum_name #ty_generics> {
+                match message {
+                    #(#enum_patterns => {
+                        let id = self.#write_fields.write(#struct_exprs).id;
+                        self.order.write(#write_order_path(#writable, id, ::core::marker::PhantomData));
+                        ::core::result::Result::Ok(())
+                    })*
+                    other => ::core::result::Result::Err(other),
+                }
+            }
+        }
+    } else {
+        quote! {
+            /// Writes `message` into the buffer of its variant.
+            pub fn write(&mut self, message: #enum_name #ty_generics) {
+                match message {
+                    #(#enum_patterns => {
+                        let id = self.#write_fields.write(#struct_exprs).id;
+                        self.order.write(#write_order_path(#writable, id, ::core::marker::PhantomData));
+                    })*
+                }
+            }
+        }
+    };
+
+    let outer = quote! {
+        impl #reader_impl_generics #reader_path<'w, 's, #(#param_args),*> #impl_where_clause {
+            /// Reads all unread messages of the family.
+            ///
+            /// Messages are grouped by variant in declaration order; within a variant they are
+            /// yielded in the order they were written.
+            pub fn read(&mut self) -> impl ::core::iter::Iterator<Item = #enum_name #ty_generics> + '_
+            #read_where_clause
+            {
+                self.order.clear();
+                let messages = ::core::iter::empty();
+                #(
+                    let messages = messages.chain(self.#fields.read().cloned().map(#enum_name::from));
+                )*
+                messages
+            }
+
+            /// Reads all unread messages of the family in the order they were written across
+            /// all variants.
+            ///
+            /// Messages written through the family writer come first, in the exact order of the
+            /// writes, whatever was written to the variant buffers in between. Messages written by
+            /// other means (a variant's `MessageWriter`, `write`, `commands.queue`) follow
+            /// afterwards, grouped by variant as in [`read`](Self::read).
+            pub fn read_in_write_order(&mut self) -> impl ::core::iter::Iterator<Item = #enum_name #ty_generics>
+            #read_where_clause
+            {
+                // Message ids grow with every write, so each buffer is sorted by id
+                #(
+                    let mut #fields: ::std::vec::Vec<_> = self
+                        .#fields
+                        .read_with_id()
+                        .map(|(message, id)| (id.id, ::core::option::Option::Some(message)))
+                        .collect();
+                )*
+                let mut messages = ::std::vec::Vec::new();
+                for order in self.order.read() {
+                    let message = match order.0 {
+                        #(#indices => #fields
+                            .binary_search_by_key(&order.1, |(id, _)| *id)
+                            .ok()
+                            .and_then(|index| #fields[index].1.take())
+                            .map(|message| #enum_name::from(message.clone())),)*
+                        _ => ::core::option::Option::None,
+                    };
+                    messages.extend(message);
+                }
+                #(messages.extend(#fields.into_iter().filter_map(|(_, message)| message).cloned().map(#enum_name::from));)*
+                messages.into_iter()
+            }
+
+            /// Returns `true` if there are no unread messages of any variant.
+            pub fn is_empty(&self) -> bool {
+                let is_empty = true;
+                #(let is_empty = is_empty && self.#fields.is_empty();)*
+                is_empty
+            }
+
+            /// Returns the number of unread messages across all variants.
+            pub fn len(&self) -> usize {
+                let len = 0;
+                #(let len = len + self.#fields.len();)*
+                len
+            }
+        }
+
+        impl #writer_impl_generics #writer_path<'w, #(#param_args),*> #impl_where_clause {
+            #write
+        }
+    };
+
+    (inner, outer)
+}
+
+/// Generates the family-wide `Any` event covering the event variants of a family.
+///
+/// `Any` mirrors the source enum with one variant per generated event struct. The module's `Plugin`
+/// triggers it for every variant event, and the conversions to and from the source enum are
+/// returned separately so they can be emitted next to the module.
+fn generate_any(
+    enum_name: &syn::Ident,
+    layout: &Layout,
+    generics: &syn::Generics,
+    shapes: &[VariantShape],
+    has_skipped: bool,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let (shapes, has_skipped) =
+        handled_shapes(shapes, has_skipped, |shape| shape.observer_kind().is_some());
+    let shapes = &shapes[..];
+    if shapes.is_empty() {
+        return (quote! {}, quote! {});
+    }
+    // `Any` only targets an entity when every event of the family does
+    let is_entity_event = shapes
+        .iter()
+        .all(|shape| shape.observer_kind() == Some(EventKind::EntityEvent));
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let variant_idents: Vec<_> = shapes.iter().map(|shape| &shape.ident).collect();
+    let struct_idents: Vec<_> = shapes.iter().map(|shape| &shape.struct_ident).collect();
+    let Layout { plugin, any, .. } = layout;
+    let ecs = &layout.module_bevy.ecs;
+    let family_vis = layout.family_vis();
+    let module_generics = layout.module_generics(generics);
+    let (module_impl_generics, _, module_where_clause) = module_generics.split_for_impl();
+
+    let event_impls = if is_entity_event {
+        let mut any_generics = module_generics.clone();
+        any_generics.make_where_clause().predicates.push(
+            syn::parse_quote! { Self: ::core::marker::Send + ::core::marker::Sync + 'static },
+        );
+        for shape in shapes {
+            let struct_ident = &shape.struct_ident;
+            any_generics
+                .make_where_clause()
+                .predicates
+                .push(syn::parse_quote! { #struct_ident #ty_generics: #ecs::event::EntityEvent });
+        }
+        let any_where_clause = &any_generics.where_clause;
+        quote! {
+            impl #module_impl_generics #ecs::event::Event for #any #ty_generics #any_where_clause {
+                type Trigger<'a> = #ecs::event::EntityTrigger;
+            }
+
+            impl #module_impl_generics #ecs::event::EntityEvent for #any #ty_generics #any_where_clause {
+                fn event_target(&self) -> #ecs::entity::Entity {
+                    match self {
+                        #(Self::#variant_idents(event) => #ecs::event::EntityEvent::event_target(event),)*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let event_derive = (!is_entity_event).then(|| {
+        let event_derive = layout.derive("Event");
+        quote! { #event_derive, }
+    });
+    let clone_derive = shapes
+        .iter()
+        .all(|shape| shape.is_clone)
+        .then(|| quote! { Clone, });
+    let debug_derive = shapes
+        .iter()
+        .all(|shape| shape.is_debug)
+        .then(|| quote! { Debug });
+    let any_doc = if layout.bevy.app.is_some() {
+        format!(
+            "Any event of this family, triggered for every variant event once [`{plugin}`] is added."
+        )
+    } else {
+        "Any event of this family, with one variant per event struct.".to_string()
+    };
+
+    let inner = quote! {
+        #[doc = #any_doc]
+        #[allow(unused_lifetimes, unused_type_parameters)]
+        #[derive(#event_derive #clone_derive #debug_derive)]
+        #family_vis enum #any #module_impl_generics #module_where_clause {
+            #(#[allow(missing_docs)] #variant_idents(#struct_idents #ty_generics),)*
+        }
+
+        #event_impls
+    };
+
+    // Skipped and message variants have no `Any` counterpart
+    let convertible: Vec<_> = shapes
+        .iter()
+        .filter(|shape| !shape.injects_entity)
+        .collect();
+    let enum_patterns = convertible.iter().map(|shape| shape.enum_tokens(enum_name));
+    let struct_exprs = convertible.iter().map(|shape| shape.struct_expr(layout));
+    let convertible_idents = convertible.iter().map(|shape| &shape.ident);
+    let any_path = layout.path(any);
+    let from_enum = if has_skipped || convertible.len() < shapes.len() {
+        quote! {
+            impl #impl_generics ::core::convert::TryFrom<#enum_name #ty_generics> for #any_path #ty_generics #where_clause {
+                /// The original value, returned unchanged when its variant is skipped, not an event or
+                /// gets an injected `entity`.
+                type Error = #enum_name #ty_generics;
+
+                #[inline]
+                #[allow(unreachable_patterns)]
+                fn try_from(value: #enum_name #ty_generics) -> ::core::result::Result<Self, Self::Error> {
+                    match value {
+                        #(#enum_patterns => ::core::result::Result::Ok(Self::#convertible_idents(#struct_exprs)),)*
+                        other => ::core::result::Result::Err(other),
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {
+            impl #impl_generics ::core::convert::From<#enum_name #ty_generics> for #any_path #ty_generics #where_clause {
+                #[inline]
+                fn from(value: #enum_name #ty_generics) -> Self {
+                    match value {
+                        #(#enum_patterns => Self::#convertible_idents(#struct_exprs),)*
+                    }
+                }
+            }
+        }
+    };
+
+    let outer = quote! {
+        impl #impl_generics ::core::convert::From<#any_path #ty_generics> for #enum_name #ty_generics #where_clause {
+            #[inline]
+            fn from(value: #any_path #ty_generics) -> Self {
+                match value {
+                    #(#any_path::#variant_idents(event) => Self::from(event),)*
+                }
+            }
+        }
+
+        #from_enum
+    };
+
+    (inner, outer)
+}
+
+/// Integer `#[repr(..)]` of the source enum, reused by the kind enum so explicit
+/// discriminants keep their type.
+fn integer_repr(attrs: &[Attribute]) -> Option<syn::Ident> {
+    const INTEGER_REPRS: [&str; 12] = [
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+
+    let mut repr = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident() {
+                if INTEGER_REPRS.contains(&ident.to_string().as_str()) {
+                    repr = Some(ident.clone());
+                }
+            }
+            if meta.input.peek(syn::token::Paren) {
+                let _content;
+                syn::parenthesized!(_content in meta.input);
+            }
+            Ok(())
+        });
+    }
+    repr
+}
+
+fn generate_kind(
+    enum_name: &syn::Ident,
+    layout: &Layout,
+    generics: &syn::Generics,
+    shapes: &[VariantShape],
+    repr: Option<&syn::Ident>,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let variant_idents: Vec<_> = shapes.iter().map(|shape| &shape.ident).collect();
+    let generated: Vec<_> = shapes.iter().filter(|shape| !shape.skip).collect();
+    let struct_idents = generated.iter().map(|shape| &shape.struct_ident);
+    let struct_kinds = generated.iter().map(|shape| &shape.ident);
+    let variant_names = variant_idents.iter().map(ToString::to_string);
+    let variant_count = shapes.len();
+    let kind_variants = shapes.iter().map(|shape| {
+        let ident = &shape.ident;
+        match &shape.discriminant {
+            Some(discriminant) => {
+                let discriminant =
+                    layout.module_const(discriminant, format_args!("{ident}_discriminant"));
+                quote! { #ident = #discriminant }
+            }
+            None => quote! { #ident },
+        }
+    });
+    let repr_attr = repr.map(|repr| quote! { #[repr(#repr)] });
+    let module_generics = layout.module_generics(generics);
+    let (module_impl_generics, _, module_where_clause) = module_generics.split_for_impl();
+    let kind = &layout.kind;
+    let kind_path = layout.path(kind);
+    let family_vis = layout.family_vis();
+    let enum_patterns = shapes.iter().map(|shape| {
+        let ident = &shape.ident;
+        match shape.style {
+            VariantStyle::Unit => quote! { #enum_name::#ident },
+            VariantStyle::Tuple => quote! { #enum_name::#ident(..) },
+            VariantStyle::Named => quote! { #enum_name::#ident { .. } },
+        }
+    });
+
+    let inner = quote! {
+        /// Payload-free discriminant of this family, with one variant per enum variant.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #repr_attr
+        #family_vis enum #kind {
+            #(#[allow(missing_docs)] #kind_variants,)*
+        }
+
+        impl #kind {
+            /// Every kind, in declaration order.
+            pub const ALL: [Self; #variant_count] = [#(Self::#variant_idents),*];
+
+            /// Name of the enum variant this kind stands for.
+            #[must_use]
+            pub const fn name(self) -> &'static str {
+                match self {
+                    #(Self::#variant_idents => #variant_names,)*
+                }
+            }
+        }
+
+        #(
+            impl #module_impl_generics #struct_idents #ty_generics #module_where_clause {
+                /// Payload-free kind of this event.
+                #[inline]
+                #[must_use]
+                pub const fn kind(&self) -> #kind {
+                    #kind::#struct_kinds
+                }
+            }
+        )*
+    };
+
+    let outer = quote! {
+        impl #impl_generics #enum_name #ty_generics #where_clause {
+            /// Payload-free kind of this value.
+            #[inline]
+            #[must_use]
+            pub const fn kind(&self) -> #kind_path {
+                match *self {
+                    #(#enum_patterns => #kind_path::#variant_idents,)*
+                }
+            }
+        }
+    };
+
+    (inner, outer)
+}
+
+/// Parses `deref`, `deref = <bool>` or `no_deref`. Returns `None` if `meta` is some other key.
+fn parse_deref(meta: &syn::meta::ParseNestedMeta) -> syn::Result<Option<bool>> {
+    if path_ends_with_ident(&meta.path, "no_deref") {
+        Ok(Some(false))
+    } else if path_ends_with_ident(&meta.path, "deref") {
+        if meta.input.peek(syn::Token![=]) {
+            Ok(Some(meta.value()?.parse::<syn::LitBool>()?.value))
+        } else {
+            Ok(Some(true))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+/// Parses the value of `kind = ...` or `also = ...`.
+fn parse_event_kind(meta: &syn::meta::ParseNestedMeta) -> syn::Result<EventKind> {
+    let kind: syn::Ident = meta.value()?.parse()?;
+    match kind.to_string().as_str() {
+        "message" => Ok(EventKind::Message),
+        "event" => Ok(EventKind::Event),
+        "entity" => Ok(EventKind::EntityEvent),
+        _ => Err(syn::Error::new(
+            kind.span(),
+            "expected `message`, `event` or `entity`",
+        )),
+    }
+}
+
+/// Turns a `target = expr` into the body of `event_target(&self)`.
+///
+/// A leading field name is shorthand for `self.field`, so `hit.victim` or
+/// `owner.unwrap_or(Entity::PLACEHOLDER)` read the variant's own fields.
+fn computed_target(expr: syn::Expr, field_names: &[syn::Ident]) -> syn::Expr {
+    match expr {
+        syn::Expr::Field(mut field) => {
+            *field.base = computed_target(*field.base, field_names);
+            syn::Expr::Field(field)
+        }
+        syn::Expr::MethodCall(mut call) => {
+            *call.receiver = computed_target(*call.receiver, field_names);
+            syn::Expr::MethodCall(call)
+        }
+        syn::Expr::Path(path)
+            if path.qself.is_none()
+                && path
+                    .path
+                    .get_ident()
+                    .is_some_and(|ident| field_names.contains(ident)) =>
+        {
+            let ident = &path.path.segments[0].ident;
+            syn::parse_quote! { self.#ident }
+        }
+        other => other,
+    }
+}
+
+/// Parses `vis = "..."`; an empty string means private.
+fn parse_vis(meta: &syn::meta::ParseNestedMeta) -> syn::Result<syn::Visibility> {
+    let vis: syn::LitStr = meta.value()?.parse()?;
+    if vis.value().trim().is_empty() {
+        Ok(syn::Visibility::Inherited)
+    } else {
+        vis.parse()
+    }
+}
+
+fn analyze_field_attrs(attrs: &[Attribute]) -> syn::Result<FieldAttrInfo> {
+    let mut info = FieldAttrInfo::default();
+
+    for attr in attrs {
+        if path_ends_with_ident(attr.path(), "enum_event") {
+            attr.parse_nested_meta(|meta| {
+                info.keys.record(&meta.path);
+                if path_ends_with_ident(&meta.path, "deref") {
+                    info.has_deref = true;
+                } else if path_ends_with_ident(&meta.path, "deref_mut") {
+                    info.has_deref_mut = true;
+                    info.has_deref = true;
+                } else if path_ends_with_ident(&meta.path, "target") {
+                    info.is_event_target = true;
+                } else if path_ends_with_ident(&meta.path, "vis") {
+                    info.vis = Some(parse_vis(&meta)?);
+                } else if path_ends_with_ident(&meta.path, "default") {
+                    info.default = Some(if meta.input.peek(syn::Token![=]) {
+                        meta.value()?.parse()?
+                    } else {
+                        syn::parse_quote! { ::core::default::Default::default() }
+                    });
+                } else {
+                    return Err(unknown_key(&meta, "a field", FIELD_KEYS));
+                }
+                Ok(())
+            })?;
+        } else if path_ends_with_ident(attr.path(), "event_target") {
+            info.keys.record(attr.path());
+            info.is_event_target = true;
+        } else if path_ends_with_ident(attr.path(), "deref") {
+            info.keys.record(attr.path());
+            info.has_deref = true;
+        } else if path_ends_with_ident(attr.path(), "deref_mut") {
+            info.keys.record(attr.path());
+            info.has_deref_mut = true;
+            info.has_deref = true;
+        } else {
+            info.passthrough_attrs.push(attr.clone());
+        }
+    }
+
+    Ok(info)
+}
+
+fn analyze_variant_attrs(attrs: &[Attribute]) -> syn::Result<VariantAttrInfo> {
+    let mut info = VariantAttrInfo::default();
+
+    for attr in attrs {
+        if path_ends_with_ident(attr.path(), "enum_event") {
+            attr.parse_nested_meta(|meta| {
+                info.keys.record(&meta.path);
+                if path_ends_with_ident(&meta.path, "auto_propagate") {
+                    info.has_auto_propagate = true;
+                    Ok(())
+                } else if path_ends_with_ident(&meta.path, "propagate") {
+                    if meta.input.peek(syn::Token![=]) {
+                        // Parse: propagate = <value>
+                        meta.input.parse::<syn::Token![=]>()?;
+                        let ty: syn::Type = meta.input.parse()?;
+                        info.propagate_value = Some(quote! { #ty });
+                    } else {
+                        // Just: propagate (no value, uses default)
+                        info.propagate_value = Some(quote! {});
+                    }
+                    Ok(())
+                } else if path_ends_with_ident(&meta.path, "rename") {
+                    let name: syn::LitStr = meta.value()?.parse()?;
+                    info.rename = Some(name.parse()?);
+                    Ok(())
+                } else if path_ends_with_ident(&meta.path, "vis") {
+                    info.vis = Some(parse_vis(&meta)?);
+                    Ok(())
+                } else if path_ends_with_ident(&meta.path, "skip") {
+                    info.skip = true;
+                    Ok(())
+                } else if path_ends_with_ident(&meta.path, "kind") {
+                    info.kind = Some(parse_event_kind(&meta)?);
+                    Ok(())
+                } else if path_ends_with_ident(&meta.path, "also") {
+                    info.also = Some((parse_event_kind(&meta)?, meta.path.span()));
+                    Ok(())
+                } else if let Some(deref) = parse_deref(&meta)? {
+                    info.deref = Some((deref, meta.path.span()));
+                    Ok(())
+                } else if path_ends_with_ident(&meta.path, "target")
+                    && meta.input.peek(syn::Token![=])
+                {
+                    info.target = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if info.derives.parse(&meta)? {
+                    Ok(())
+                } else if path_ends_with_ident(&meta.path, "forward_attrs") {
+                    parse_attr_paths(&meta, &mut info.forward_attrs)
+                } else if path_ends_with_ident(&meta.path, "exclude_attrs") {
+                    parse_attr_paths(&meta, &mut info.exclude_attrs)
+                } else if path_ends_with_ident(&meta.path, "target") {
+                    Err(meta.error(
+                        "`target` on a variant computes the target: `target = field.entity`; to use a field as it is, mark the field with #[enum_event(target)]",
+                    ))
+                } else {
+                    Err(unknown_key(&meta, "a variant", VARIANT_KEYS))
+                }
+            })?;
+        } else if !attr.path().is_ident("default") {
+            // `#[default]` belongs to the enum's `Default` derive
+            info.passthrough_attrs.push(attr.clone());
+        }
+    }
+
+    Ok(info)
+}
+
+/// Parses the attribute list of `forward_attrs(serde, strum)` or `exclude_attrs(deprecated)`:
+/// variant attributes that are additionally copied onto the generated structs, or left out.
+fn parse_attr_paths(
+    meta: &syn::meta::ParseNestedMeta,
+    attr_paths: &mut Vec<syn::Path>,
+) -> syn::Result<()> {
+    meta.parse_nested_meta(|attr| {
+        attr_paths.push(attr.path);
+        Ok(())
+    })
+}
+
+/// Parses `rename_items(kind = "FilterKind", reader = "JobReader")`: names for the family
+/// helper items, which are then used as given.
+fn parse_item_names(
+    meta: &syn::meta::ParseNestedMeta,
+    item_names: &mut Vec<(String, syn::Ident)>,
+) -> syn::Result<()> {
+    meta.parse_nested_meta(|item| {
+        let key = item
+            .path
+            .get_ident()
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        if !ITEM_KEYS.contains(&key.as_str()) {
+            return Err(item.error(format!(
+                "unknown item `{key}` in `rename_items`; expected one of {}",
+                ITEM_KEYS
+                    .iter()
+                    .map(|known| format!("`{known}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+        let name: syn::LitStr = item.value()?.parse()?;
+        item_names.push((key, name.parse()?));
+        Ok(())
+    })
+}
+
+/// Marks every generated item with `#[allow(deprecated)]`, so deprecated variants and their
+/// structs only warn where user code names them.
+fn allow_deprecated(tokens: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let Ok(mut file) = syn::parse2::<syn::File>(tokens.clone()) else {
+        return tokens;
+    };
+    for item in &mut file.items {
+        let attrs = match item {
+            syn::Item::Const(item) => &mut item.attrs,
+            syn::Item::Enum(item) => &mut item.attrs,
+            syn::Item::Impl(item) => &mut item.attrs,
+            syn::Item::Mod(item) => &mut item.attrs,
+            syn::Item::Struct(item) => &mut item.attrs,
+            _ => continue,
+        };
+        attrs.push(syn::parse_quote! { #[allow(deprecated)] });
+    }
+    quote! { #file }
+}
+
+/// Generates Bevy `Event` types from enum variants for observer-based events.
+///
+/// Creates a snake_case module with one event struct per variant.
+/// These events are triggered via `world.trigger()` and handled by observers.
+///
+/// ```rust
+/// use bevy_enum_event::EnumEvent;
+///
+/// #[derive(EnumEvent, Clone)]
+/// enum Action {
+///     Jump,
+///     Run(f32),
+///     Attack { damage: i32, critical: bool },
+/// }
+/// // Generates: action::Jump, action::Run, action::Attack
+/// // Each struct derives Event for use with triggers/observers
+/// ```
+#[proc_macro_derive(EnumEvent, attributes(enum_event, deref, deref_mut))]
+pub fn derive_enum_events(input: TokenStream) -> TokenStream {
+    derive_enum_event_impl(input, EventKind::Event)
+}
+
+/// Generates Bevy `Message` types from enum variants for buffered message passing.
+///
+/// Creates a snake_case module with one message struct per variant.
+/// These messages are written via `MessageWriter` and read via `MessageReader`.
+/// Each generated type must be registered with `app.add_message::<T>()`; the generated
+/// `Plugin` does this for every variant.
+///
+/// ```rust
+/// use bevy_enum_event::EnumMessage;
+///
+/// #[derive(EnumMessage, Clone)]
+/// enum NetworkMessage {
+///     Connected(String),
+///     Disconnected { reason: String },
+///     DataReceived { data: Vec<u8> },
+/// }
+/// // Generates: network_message::Connected, network_message::Disconnected, network_message::DataReceived
+/// // Each struct derives Message for use with MessageWriter/MessageReader
+/// ```
+#[proc_macro_derive(EnumMessage, attributes(enum_event, deref, deref_mut))]
+pub fn derive_enum_messages(input: TokenStream) -> TokenStream {
+    derive_enum_event_impl(input, EventKind::Message)
+}
+
+/// Generates Bevy `EntityEvent` types from enum variants.
+///
+/// Each variant needs a target: an `entity: Entity` field, a field marked with
+/// `#[enum_event(target)]`, a single `Entity` tuple field, `#[enum_event(target = expr)]`, or
+/// `#[enum_event(inject_entity)]` on the enum.
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_enum_event::EnumEntityEvent;
+///
+/// #[derive(EnumEntityEvent, Clone, Copy)]
+/// enum PlayerEvent {
+///     Spawned { entity: Entity },
+///     Damaged { entity: Entity, amount: f32 },
+/// }
+/// # fn main() {}
+/// ```
+///
+/// # Propagation
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_enum_event::EnumEntityEvent;
+///
+/// #[derive(EnumEntityEvent, Clone, Copy)]
+/// #[enum_event(propagate)]
+/// enum UiEvent {
+///     Click { entity: Entity },
+/// }
+///
+/// #[derive(EnumEntityEvent, Clone, Copy)]
+/// #[enum_event(auto_propagate, propagate)]
+/// enum SystemEvent {
+///     Update { entity: Entity },
+/// }
+/// # fn main() {}
+/// ```
+#[proc_macro_derive(
+    EnumEntityEvent,
+    attributes(enum_event, event_target, deref, deref_mut)
+)]
+pub fn derive_enum_entity_events(input: TokenStream) -> TokenStream {
+    derive_enum_event_impl(input, EventKind::EntityEvent)
+}
+
+/// Specifies which kind of Bevy event/message to generate.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum EventKind {
+    /// Observer-based global events (`#[derive(Event)]`)
+    Event,
+    /// Buffered messages (`#[derive(Message)]`)
+    Message,
+    /// Entity-targeted observer events (`#[derive(EntityEvent)]`)
+    EntityEvent,
+}
+
+impl EventKind {
+    /// Name of the derive macro for this kind.
+    fn derive_name(self) -> &'static str {
+        match self {
+            EventKind::Event => "EnumEvent",
+            EventKind::Message => "EnumMessage",
+            EventKind::EntityEvent => "EnumEntityEvent",
+        }
+    }
+
+    /// Name of this kind in `kind = ...` and `also = ...`.
+    fn key(self) -> &'static str {
+        match self {
+            EventKind::Event => "event",
+            EventKind::Message => "message",
+            EventKind::EntityEvent => "entity",
+        }
+    }
+}
+
+/// The derive that expanded each enum in this compilation, keyed by the span of its name.
+///
+/// A derive can't see the `#[derive(...)]` list it was called from, and two of them on one enum
+/// would generate the same module and impls twice. The second one reports a single error
+/// instead. Re-expanding with the same derive, as IDEs do, is fine.
+static EXPANDED: Mutex<BTreeMap<String, EventKind>> = Mutex::new(BTreeMap::new());
+
+/// Records that `event_kind` expands the enum `ident`, or explains why it can't.
+fn claim_enum(ident: &syn::Ident, event_kind: EventKind) -> syn::Result<()> {
+    let key = format!("{ident} {:?}", ident.span());
+    let mut expanded = EXPANDED
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let first = *expanded.entry(key).or_insert(event_kind);
+    if first == event_kind {
+        return Ok(());
+    }
+    let (first_name, second_name, key) = (
+        first.derive_name(),
+        event_kind.derive_name(),
+        event_kind.key(),
+    );
+    // `also` pairs messages with events, `kind` mixes the two observer kinds
+    let hint = if first == EventKind::Message || event_kind == EventKind::Message {
+        format!("derive only `{first_name}` and add `#[enum_event(also = {key})]` to the enum")
+    } else {
+        format!("derive only `{first_name}` and mark the variants of the other kind with `#[enum_event(kind = {key})]`")
+    };
+    Err(syn::Error::new(
+        Span::call_site(),
+        format!("`{ident}` already derives `{first_name}`, so it can't derive `{second_name}` too; {hint}"),
+    ))
+}
+
+fn derive_enum_event_impl(input: TokenStream, event_kind: EventKind) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    claim_enum(&input.ident, event_kind)
+        .and_then(|()| expand_enum_event(&input, event_kind))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Expands one of the derives, or reports every mistake in `input` at once.
+#[allow(clippy::too_many_lines)]
+fn expand_enum_event(
+    input: &DeriveInput,
+    event_kind: EventKind,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let enum_name = &input.ident;
+    let mut errors = Errors::default();
+
+    // Check for propagate and auto_propagate attributes on the enum
+    // Can be: #[enum_event(propagate)]
+    //         #[enum_event(propagate = &'static RelType)]
+    //         #[enum_event(auto_propagate, propagate = &'static RelType)]
+    let mut propagate_value: Option<proc_macro2::TokenStream> = None;
+    let mut has_auto_propagate = false;
+    // Naming: #[enum_event(module = "name", prefix, prefix = "Pre", suffix = "Event")]
+    //         #[enum_event(flat, suffix = "Event")]
+    let mut module_override: Option<syn::Ident> = None;
+    let mut is_flat = false;
+    // Visibility of the generated items, inherited from the enum unless overridden
+    let mut vis_override: Option<syn::Visibility> = None;
+    // Derives: #[enum_event(derive(PartialEq, Hash), no_clone, no_debug, forward_derives)]
+    let mut derive_options = DeriveOptions::default();
+    let mut forward_derives = false;
+    // Variant attributes copied beyond the defaults: #[enum_event(forward_attrs(serde))]
+    let mut forward_attrs: Vec<syn::Path> = Vec::new();
+    // Variant attributes that stay on the enum: #[enum_event(exclude_attrs(deprecated))]
+    let mut exclude_attrs: Vec<syn::Path> = Vec::new();
+    // Names of the helper items: #[enum_event(rename_items(kind = "FilterKind"))]
+    let mut item_names: Vec<(String, syn::Ident)> = Vec::new();
+    let mut struct_prefix = String::new();
+    let mut struct_suffix = String::new();
+    // Bevy crate the generated code refers to: #[enum_event(crate = bevy_ecs)]
+    let mut crate_path: Option<syn::Path> = None;
+    // Second kind of every struct: #[enum_event(also = message)]
+    let mut also: Option<(EventKind, Span)> = None;
+    // Deref policy, the `deref` feature unless set: #[enum_event(deref = false)], no_deref
+    let mut deref_policy: Option<(bool, Span)> = None;
+    // Target-less entity variants get an `entity` field: #[enum_event(inject_entity)]
+    let mut inject_entity = false;
+    let mut enum_keys = AttrKeys::default();
+
+    for attr in &input.attrs {
+        if path_ends_with_ident(attr.path(), "enum_event") {
+            let parsed = attr.parse_nested_meta(|meta| {
+                enum_keys.record(&meta.path);
+                if path_ends_with_ident(&meta.path, "auto_propagate") {
+                    has_auto_propagate = true;
+                    Ok(())
+                } else if path_ends_with_ident(&meta.path, "propagate") {
+                    if meta.input.peek(syn::Token![=]) {
+                        // Parse: propagate = <value>
+                        meta.input.parse::<syn::Token![=]>()?;
+                        let ty: syn::Type = meta.input.parse()?;
+                        propagate_value = Some(quote! { #ty });
+                    } else {
+                        // Just: propagate (no value, uses default)
+                        propagate_value = Some(quote! {});
+                    }
+                    Ok(())
+                } else if path_ends_with_ident(&meta.path, "flat") {
+                    is_flat = true;
+                    Ok(())
+                } else if path_ends_with_ident(&meta.path, "inject_entity") {
+                    inject_entity = true;
+                    Ok(())
+                } else if path_ends_with_ident(&meta.path, "also") {
+                    also = Some((parse_event_kind(&meta)?, meta.path.span()));
+                    Ok(())
+                } else if let Some(deref) = parse_deref(&meta)? {
+                    deref_policy = Some((deref, meta.path.span()));
+                    Ok(())
+                } else if meta.path.is_ident("crate") {
+                    crate_path = Some(meta.value()?.call(syn::Path::parse_mod_style)?);
+                    Ok(())
+                } else if path_ends_with_ident(&meta.path, "vis") {
+                    vis_override = Some(parse_vis(&meta)?);
+                    Ok(())
+                } else if path_ends_with_ident(&meta.path, "forward_derives") {
+                    forward_derives = true;
+                    Ok(())
+                } else if derive_options.parse(&meta)? {
+                    Ok(())
+                } else if path_ends_with_ident(&meta.path, "forward_attrs") {
+                    parse_attr_paths(&meta, &mut forward_attrs)
+                } else if path_ends_with_ident(&meta.path, "exclude_attrs") {
+                    parse_attr_paths(&meta, &mut exclude_attrs)
+                } else if path_ends_with_ident(&meta.path, "rename_items") {
+                    parse_item_names(&meta, &mut item_names)
+                } else if path_ends_with_ident(&meta.path, "module") {
+                    let name: syn::LitStr = meta.value()?.parse()?;
+                    module_override = Some(name.parse()?);
+                    Ok(())
+                } else if path_ends_with_ident(&meta.path, "prefix") {
+                    // A bare `prefix` uses the enum name
+                    struct_prefix = if meta.input.peek(syn::Token![=]) {
+                        meta.value()?.parse::<syn::LitStr>()?.value()
+                    } else {
+                        enum_name.to_string()
+                    };
+                    Ok(())
+                } else if path_ends_with_ident(&meta.path, "suffix") {
+                    struct_suffix = meta.value()?.parse::<syn::LitStr>()?.value();
+                    Ok(())
+                } else {
+                    Err(unknown_key(&meta, "the enum", ENUM_KEYS))
+                }
+            });
+            if let Err(error) = parsed {
+                errors.push(error);
+            }
+        }
+    }
+
+    // Extract variants from enum
+    let variants = match &input.data {
+        Data::Enum(data_enum) => &data_enum.variants,
+        Data::Struct(data) => {
+            return Err(syn::Error::new(
+                data.struct_token.span,
+                "bevy_enum_event derives can only be used on enums",
+            ))
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span,
+                "bevy_enum_event derives can only be used on enums",
+            ))
+        }
+    };
+
+    if let Some((also, span)) = also {
+        errors.check_at(
+            (also == EventKind::Message) != (event_kind == EventKind::Message),
+            span,
+            "`also` must add the other kind: `message` to events, `event` or `entity` to messages",
+        );
+    }
+    if let (true, Some(module)) = (is_flat, &module_override) {
+        errors.push(syn::Error::new_spanned(
+            module,
+            "`flat` emits no module, so it can't be combined with `module = \"...\"`",
+        ));
+    }
+    let also = also.map(|(also, _)| also);
+
+    // Convert EnumName to snake_case for module name, unless overridden or flat
+    let module_name = (!is_flat).then(|| {
+        module_override.unwrap_or_else(|| {
+            syn::Ident::new(&to_snake_case(&enum_name.to_string()), enum_name.span())
+        })
+    });
+    let layout = Layout::new(
+        enum_name,
+        module_name,
+        vis_override.unwrap_or_else(|| input.vis.clone()),
+        crate_path.as_ref(),
+        &input.generics,
+        &item_names,
+    );
+    if let Some((true, span)) = deref_policy {
+        errors.check_at(
+            layout.bevy.deref.is_some(),
+            span,
+            "`deref` needs the `Deref` derives; add `bevy_derive` as a dependency",
+        );
+    }
+    let deref_policy = deref_policy.map(|(deref, _)| deref);
+
+    let forwarded_derives = if forward_derives {
+        enum_derives(&input.attrs)
+    } else {
+        Vec::new()
+    };
+
+    let generics = input.generics.clone();
+    let module_generics = layout.module_generics(&generics);
+    let struct_generics = if module_generics.params.is_empty() {
+        quote! {}
+    } else {
+        let params = module_generics.params.iter();
+        quote! { <#(#params),*> }
+    };
+    let where_clause = module_generics.where_clause.as_ref();
+    let type_params: Vec<(String, syn::Ident)> = generics
+        .type_params()
+        .map(|param| (param.ident.to_string(), param.ident.clone()))
+        .collect();
+    let lifetime_params: Vec<(String, syn::Lifetime)> = generics
+        .lifetimes()
+        .map(|param| {
+            let lt = param.lifetime.clone();
+            (lt.ident.to_string(), lt)
+        })
+        .collect();
+    let type_param_names: Vec<String> = type_params.iter().map(|(name, _)| name.clone()).collect();
+    let lifetime_param_names: Vec<String> = lifetime_params
+        .iter()
+        .map(|(name, _)| name.clone())
+        .collect();
+
+    // Generate struct definitions for each variant
+    let mut struct_defs = Vec::new();
+    let mut additional_impls = Vec::new();
+    // Items for `__scope`, and items next to the enum
+    let mut scope_items = Vec::new();
+    let mut parent_items = Vec::new();
+    let mut has_entity_events = false;
+    let mut shapes = Vec::new();
+
+    // Attribute errors of every variant and field are reported together
+    let mut parsed_attrs = Vec::new();
+    for variant in variants {
+        let variant_attr_info = analyze_variant_attrs(&variant.attrs).unwrap_or_else(|error| {
+            errors.push(error);
+            VariantAttrInfo::default()
+        });
+        let field_attr_infos: Vec<_> = variant
+            .fields
+            .iter()
+            .map(|field| {
+                analyze_field_attrs(&field.attrs).unwrap_or_else(|error| {
+                    errors.push(error);
+                    FieldAttrInfo::default()
+                })
+            })
+            .collect();
+        parsed_attrs.push((variant_attr_info, field_attr_infos));
+    }
+
+    for (variant, (variant_attr_info, field_attr_infos)) in variants.iter().zip(&parsed_attrs) {
+        let variant_ident = &variant.ident;
+        let struct_generics_tokens = struct_generics.clone();
+
+        if variant_attr_info.skip {
+            for (key, span) in variant_attr_info
+                .keys
+                .0
+                .iter()
+                .chain(field_attr_infos.iter().flat_map(|info| &info.keys.0))
+                .filter(|(key, _)| key != "skip")
+            {
+                errors.push(syn::Error::new(
+                    *span,
+                    format!("variant `{variant_ident}` is skipped, so `{key}` has no effect"),
+                ));
+            }
+            // Skipped variants only show up in the kind enum
+            let mut shape = VariantShape::new(
+                variant,
+                variant_ident.clone(),
+                event_kind,
+                None,
+                false,
+                &[],
+                field_attr_infos,
+            );
+            shape.skip = true;
+            shapes.push(shape);
+            continue;
+        }
+        let variant_kind = variant_attr_info.kind.unwrap_or(event_kind);
+        if let Some((also, span)) = variant_attr_info.also {
+            errors.check_at(
+                (also == EventKind::Message) != (variant_kind == EventKind::Message),
+                span,
+                format!("variant `{variant_ident}` can't be both kinds given by `kind` and `also`; one of them must be `message` and the other `event` or `entity`"),
+            );
+        }
+        // The enum's `also` only adds to variants of the other kind
+        let variant_also = variant_attr_info.also.map(|(also, _)| also).or_else(|| {
+            also.filter(|also| {
+                (*also == EventKind::Message) != (variant_kind == EventKind::Message)
+            })
+        });
+        let is_entity_event =
+            variant_kind == EventKind::EntityEvent || variant_also == Some(EventKind::EntityEvent);
+        has_entity_events |= is_entity_event;
+        if !is_entity_event {
+            let variant_keys = variant_attr_info
+                .keys
+                .0
+                .iter()
+                .filter(|(key, _)| key == "propagate" || key == "auto_propagate");
+            let field_keys = field_attr_infos
+                .iter()
+                .flat_map(|info| &info.keys.0)
+                .filter(|(key, _)| key == "target" || key == "event_target");
+            for (key, span) in variant_keys.chain(field_keys) {
+                errors.push(syn::Error::new(
+                    *span,
+                    format!("`{key}` only applies to entity events, and variant `{variant_ident}` isn't one"),
+                ));
+            }
+        }
+        if let Some(span) = variant_attr_info.keys.span(&["auto_propagate"]) {
+            errors.check_at(
+                variant_attr_info.propagate_value.is_some(),
+                span,
+                format!(
+                    "`auto_propagate` on variant `{variant_ident}` needs `propagate` next to it"
+                ),
+            );
+        }
+
+        let deref_fields: Vec<_> = variant
+            .fields
+            .iter()
+            .zip(field_attr_infos)
+            .filter(|(_, info)| info.has_deref)
+            .map(|(field, _)| field)
+            .collect();
+        if let Some(field) = deref_fields.get(1) {
+            errors.push(syn::Error::new_spanned(
+                field,
+                format!("variant `{variant_ident}` has multiple fields marked for deref (e.g., #[enum_event(deref)]); only one field can be dereferenced"),
+            ));
+        }
+        if let Some((true, span)) = variant_attr_info.deref {
+            errors.check_at(
+                layout.bevy.deref.is_some(),
+                span,
+                format!("`deref` on `{variant_ident}` needs the `Deref` derives; add `bevy_derive` as a dependency"),
+            );
+            errors.check_at(
+                matches!(variant.fields, Fields::Unit)
+                    || variant.fields.len() == 1
+                    || deref_fields.len() == 1,
+                span,
+                format!("variant `{variant_ident}` has several fields; mark the one to dereference with #[enum_event(deref)]"),
+            );
+        }
+        if let (true, Some((_, span))) = (variant.fields.is_empty(), variant_attr_info.deref) {
+            errors.push(syn::Error::new(
+                span,
+                format!("variant `{variant_ident}` has no fields, so `deref` has no effect"),
+            ));
+        }
+        let deref_policy = variant_attr_info
+            .deref
+            .map(|(deref, _)| deref)
+            .or(deref_policy);
+        let deref_on = deref_policy.unwrap_or(cfg!(feature = "deref"));
+        for info in field_attr_infos {
+            if let Some(span) = info.keys.span(&["deref", "deref_mut"]) {
+                errors.check_at(
+                    layout.bevy.deref.is_some(),
+                    span,
+                    "`deref` needs the `Deref` derives; add `bevy_derive` as a dependency",
+                );
+                let message = match deref_policy {
+                    Some(_) => format!("`deref` is turned off for variant `{variant_ident}`, so marking a field has no effect"),
+                    None => format!("the `deref` feature is off, so marking a field has no effect; add #[enum_event(deref)] to variant `{variant_ident}` or the enum to derive `Deref` anyway"),
+                };
+                errors.check_at(deref_on, span, message);
+            }
+        }
+        // Single fields and marked fields follow the policy
+        let derive_deref = |field_count: usize, deref_attr_fields: usize| {
+            layout.bevy.deref.is_some() && deref_on && (deref_attr_fields == 1 || field_count == 1)
+        };
+        let variant_vis = variant_attr_info
+            .vis
+            .clone()
+            .unwrap_or_else(|| layout.vis.clone());
+        let struct_vis = layout.item_vis(&variant_vis);
+        let struct_ident = variant_attr_info.rename.clone().unwrap_or_else(|| {
+            syn::Ident::new(
+                &format!("{struct_prefix}{variant_ident}{struct_suffix}"),
+                variant_ident.span(),
+            )
+        });
+
+        // Determine propagate settings for this variant:
+        // - If variant has propagate settings, use those (override enum-level)
+        // - Otherwise, use enum-level settings
+        let variant_has_propagate = variant_attr_info.propagate_value.is_some();
+        let variant_propagate_value = if variant_has_propagate {
+            variant_attr_info.propagate_value.clone()
+        } else {
+            propagate_value.clone()
+        };
+        let variant_auto_propagate = if variant_has_propagate {
+            variant_attr_info.has_auto_propagate
+        } else {
+            has_auto_propagate
+        };
+
+        let mut usage_collector =
+            GenericsUsageCollector::new(&type_param_names, &lifetime_param_names);
+        for field in &variant.fields {
+            usage_collector.visit_type(&field.ty);
+        }
+        let unused_type_params: Vec<_> = type_params
+            .iter()
+            .filter(|(name, _)| !usage_collector.used_types.contains(name))
+            .map(|(_, ident)| ident.clone())
+            .collect();
+        let unused_lifetimes: Vec<_> = lifetime_params
+            .iter()
+            .filter(|(name, _)| !usage_collector.used_lifetimes.contains(name))
+            .map(|(_, lifetime)| lifetime.clone())
+            .collect();
+        let phantom_entries: Vec<_> = unused_type_params
+            .iter()
+            .map(|ident| quote! { #ident })
+            .chain(unused_lifetimes.iter().map(|lt| {
+                quote! { &#lt () }
+            }))
+            .collect();
+        let phantom_type = if phantom_entries.is_empty() {
+            None
+        } else {
+            Some(quote! { ::core::marker::PhantomData<(#(#phantom_entries ,)*)> })
+        };
+        // A computed target replaces the field marker with a manual `EntityEvent` impl
+        if let Some(expr) = &variant_attr_info.target {
+            errors.check(
+                is_entity_event,
+                expr,
+                format!(
+                    "`target = ...` on variant `{variant_ident}` only applies to entity events"
+                ),
+            );
+            if let Some((field, _)) = variant
+                .fields
+                .iter()
+                .zip(field_attr_infos)
+                .find(|(_, info)| info.is_event_target)
+            {
+                errors.push(syn::Error::new_spanned(
+                    field,
+                    format!("variant `{variant_ident}` has both `target = ...` and a field marked as event target; use one of them"),
+                ));
+            }
+            errors.check(
+                variant_propagate_value.is_none(),
+                expr,
+                format!("variant `{variant_ident}` can't propagate with `target = ...`; propagation needs a target field to update"),
+            );
+        }
+        let computed_target = variant_attr_info.target.clone().map(|expr| {
+            let field_names: Vec<_> = variant
+                .fields
+                .iter()
+                .filter_map(|field| field.ident.clone())
+                .collect();
+            computed_target(expr, &field_names)
+        });
+        // For EntityEvent, find the target: a marked field, the `entity` field, or the only
+        // field of a tuple variant if it is an `Entity`
+        let target_field = if is_entity_event && computed_target.is_none() {
+            let target_fields: Vec<_> = variant
+                .fields
+                .iter()
+                .zip(field_attr_infos)
+                .enumerate()
+                .filter(|(_, (field, info))| {
+                    info.is_event_target || field.ident.as_ref().is_some_and(|id| id == "entity")
+                })
+                .map(|(index, _)| index)
+                .collect();
+            if let Some(&index) = target_fields.get(1) {
+                errors.push(syn::Error::new_spanned(
+                    variant.fields.iter().nth(index),
+                    format!("variant `{variant_ident}` has multiple fields marked as event target; only one field can be the target"),
+                ));
+            }
+
+            target_fields.first().copied().or_else(|| match &variant.fields {
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                    matches!(&fields.unnamed[0].ty, syn::Type::Path(ty) if path_ends_with_ident(&ty.path, "Entity"))
+                        .then_some(0)
+                }
+                _ => None,
+            })
+        } else {
+            None
+        };
+        let injects_entity = is_entity_event && target_field.is_none() && computed_target.is_none();
+
+        errors.check(
+            !injects_entity || inject_entity,
+            variant_ident,
+            format!("variant `{variant_ident}` must have an `entity: Entity` field or a field marked with #[enum_event(target)]; or add #[enum_event(inject_entity)] to the enum"),
+        );
+        let derives = derive_options.struct_derives(
+            &variant_attr_info.derives,
+            matches!(variant.fields, Fields::Unit) && !injects_entity,
+            &forwarded_derives,
+        );
+        let derives: Vec<_> = derives
+            .iter()
+            .map(|derive| layout.module_derive(derive))
+            .collect();
+        let mut shape = VariantShape::new(
+            variant,
+            struct_ident.clone(),
+            variant_kind,
+            variant_also,
+            phantom_type.is_some(),
+            &derives,
+            field_attr_infos,
+        );
+        shape.injects_entity = injects_entity;
+        // The marker of unused parameters has nothing to reflect
+        let phantom_attrs = shape.is_reflect.then(|| quote! { #[reflect(ignore)] });
+        if builder_ident(&shape).is_none() {
+            for span in field_attr_infos
+                .iter()
+                .filter_map(|info| info.keys.span(&["default"]))
+            {
+                errors.push(syn::Error::new(
+                    span,
+                    format!("`default` only applies to builders, and variant `{variant_ident}` has none; builders need named fields and no field named `build`"),
+                ));
+            }
+        }
+        shapes.push(shape);
+
+        let entity_ty = {
+            let ecs = &layout.module_bevy.ecs;
+            quote! { #ecs::prelude::Entity }
+        };
+        let injected_vis = layout.item_vis(&variant_vis);
+
+        // Note: We accept #[enum_event(propagate)] on the enum, but generate #[entity_event(propagate)]
+        // on the struct because that's what Bevy's EntityEvent derive expects
+        // Generate variant-specific propagate attributes
+        let propagate_attr = if is_entity_event && variant_propagate_value.is_some() {
+            match variant_propagate_value.clone() {
+                Some(tokens) if tokens.is_empty() => {
+                    if variant_auto_propagate {
+                        quote! { #[entity_event(auto_propagate, propagate)] }
+                    } else {
+                        quote! { #[entity_event(propagate)] }
+                    }
+                }
+                Some(tokens) => {
+                    let adjusted_tokens = match syn::parse2::<syn::Type>(tokens.clone()) {
+                        Ok(ty) => {
+                            let alias = format!("{variant_ident}_propagate");
+                            scope_items.push(layout.type_alias(&ty, &alias));
+                            let ty = layout.module_type(&ty, &alias);
+                            quote! { #ty }
+                        }
+                        Err(_) => quote! { #tokens },
+                    };
+
+                    if variant_auto_propagate {
+                        quote! { #[entity_event(auto_propagate, propagate = #adjusted_tokens)] }
+                    } else {
+                        quote! { #[entity_event(propagate = #adjusted_tokens)] }
+                    }
+                }
+                None => quote! {},
+            }
+        } else {
+            quote! {}
+        };
+
+        let event_derives = [variant_kind]
+            .into_iter()
+            .chain(variant_also)
+            .filter_map(|kind| match kind {
+                EventKind::EntityEvent => computed_target
+                    .is_none()
+                    .then(|| layout.derive("EntityEvent")),
+                EventKind::Message => Some(layout.derive("Message")),
+                EventKind::Event => Some(layout.derive("Event")),
+            });
+        let event_derive = quote! { #(#event_derives,)* };
+        let deref_derive = layout.derive("Deref");
+        let deref_mut_derive = layout.derive("DerefMut");
+
+        let struct_doc = match (variant_kind, variant_also) {
+            (EventKind::EntityEvent, None) => {
+                "Entity event type corresponding to the enum variant."
+            }
+            (EventKind::Message, None) => "Message type corresponding to the enum variant.",
+            (EventKind::Event, None) => "Event type corresponding to the enum variant.",
+            (_, Some(EventKind::EntityEvent)) | (EventKind::EntityEvent, Some(_)) => {
+                "Entity event and message type corresponding to the enum variant."
+            }
+            (_, Some(_)) => "Event and message type corresponding to the enum variant.",
+        };
+        let same_path = |a: &syn::Path, b: &syn::Path| {
+            a.segments
+                .iter()
+                .map(|segment| &segment.ident)
+                .eq(b.segments.iter().map(|segment| &segment.ident))
+        };
+        let forwarded_attrs: Vec<_> = variant_attr_info
+            .passthrough_attrs
+            .iter()
+            .filter(|attr| {
+                // `#[cfg]` always follows the variant, or the struct would outlive its payload types
+                if attr.path().is_ident("cfg") {
+                    return true;
+                }
+                let is_forwarded = FORWARDED_ATTRS
+                    .iter()
+                    .any(|forwarded| attr.path().is_ident(forwarded))
+                    || forward_attrs
+                        .iter()
+                        .chain(&variant_attr_info.forward_attrs)
+                        .any(|forwarded| same_path(attr.path(), forwarded));
+                is_forwarded
+                    && !exclude_attrs
+                        .iter()
+                        .chain(&variant_attr_info.exclude_attrs)
+                        .any(|excluded| same_path(attr.path(), excluded))
+            })
+            .collect();
+        // The variant's own docs replace the generic one
+        let generic_doc = (!forwarded_attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("doc")))
+        .then(|| quote! { #[doc = #struct_doc] });
+        // Placed below the derives, whose helper attributes may be among them
+        let struct_attrs = quote! {
+            #generic_doc
+            #(#forwarded_attrs)*
+        };
+
+        let struct_def = match &variant.fields {
+            Fields::Unit => {
+                let phantom_field = phantom_type.clone().map(|phantom_type| {
+                    quote! {
+                        #[doc(hidden)]
+                        #phantom_attrs
+                        pub(crate) _phantom: #phantom_type,
+                    }
+                });
+                if injects_entity {
+                    quote! {
+                        #[allow(unused_lifetimes, unused_type_parameters)]
+                        #[derive(#event_derive #(#derives),*)]
+                        #struct_attrs
+                        #propagate_attr
+                        #struct_vis struct #struct_ident #struct_generics_tokens #where_clause {
+                            #[event_target]
+                            #injected_vis entity: #entity_ty,
+                            #phantom_field
+                        }
+                    }
+                } else if let Some(phantom_field) = phantom_field {
+                    quote! {
+                        #[allow(unused_lifetimes, unused_type_parameters)]
+                        #[derive(#event_derive #(#derives),*)]
+                        #struct_attrs
+                        #struct_vis struct #struct_ident #struct_generics_tokens #where_clause {
+                            #phantom_field
+                        }
+                    }
+                } else {
+                    quote! {
+                        #[allow(unused_lifetimes, unused_type_parameters)]
+                        #[derive(#event_derive #(#derives),*)]
+                        #struct_attrs
+                        #struct_vis struct #struct_ident #struct_generics_tokens #where_clause;
+                    }
+                }
+            }
+            Fields::Unnamed(fields) => {
+                let struct_generics_tokens = struct_generics_tokens.clone();
+                let field_infos: Vec<_> = field_attr_infos
+                    .iter()
+                    .zip(fields.unnamed.iter().enumerate())
+                    .map(|(info, (index, field))| {
+                        (info, layout.field_type(variant_ident, index, &field.ty))
+                    })
+                    .collect();
+                let field_count = field_infos.len();
+                let deref_attr_fields = deref_fields.len();
+
+                let should_derive_deref = derive_deref(field_count, deref_attr_fields);
+
+                // The injected target or the phantom field make a single field one of several
+                let auto_mark_single_field =
+                    should_derive_deref && deref_attr_fields == 0 && field_count == 1;
+
+                let mut field_tokens: Vec<_> = field_infos
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (info, ty))| {
+                        let passthrough_attrs = info.passthrough_attrs.iter();
+                        let mut marker_attrs = Vec::new();
+
+                        if target_field == Some(index) {
+                            marker_attrs.push(quote!(#[event_target]));
+                        }
+
+                        if should_derive_deref {
+                            if info.has_deref || auto_mark_single_field {
+                                marker_attrs.push(quote!(#[deref]));
+                            }
+                            if info.has_deref_mut {
+                                marker_attrs.push(quote!(#[deref_mut]));
+                            }
+                        }
+
+                        let field_vis = layout.item_vis(info.vis.as_ref().unwrap_or(&variant_vis));
+                        quote! {
+                            #(#passthrough_attrs)*
+                            #(#marker_attrs)*
+                            #field_vis #ty
+                        }
+                    })
+                    .collect();
+
+                if injects_entity {
+                    field_tokens.push(quote! {
+                        #[event_target]
+                        #injected_vis #entity_ty
+                    });
+                }
+                if let Some(phantom_type) = phantom_type.clone() {
+                    field_tokens.push(quote! {
+                        #[doc(hidden)]
+                        #phantom_attrs
+                        pub(crate) #phantom_type
+                    });
+                }
+
+                if should_derive_deref {
+                    quote! {
+                        #[allow(unused_lifetimes, unused_type_parameters)]
+                        #[derive(#event_derive #deref_derive, #deref_mut_derive, #(#derives),*)]
+                        #struct_attrs
+                        #propagate_attr
+                        #struct_vis struct #struct_ident #struct_generics_tokens(#(#field_tokens),*) #where_clause;
+                    }
+                } else {
+                    quote! {
+                        #[allow(unused_lifetimes, unused_type_parameters)]
+                        #[derive(#event_derive #(#derives),*)]
+                        #struct_attrs
+                        #propagate_attr
+                        #struct_vis struct #struct_ident #struct_generics_tokens(#(#field_tokens),*) #where_clause;
+                    }
+                }
+            }
+            Fields::Named(fields) => {
+                let struct_generics_tokens = struct_generics_tokens.clone();
+                let field_infos: Vec<_> = field_attr_infos
+                    .iter()
+                    .zip(fields.named.iter().enumerate())
+                    .map(|(info, (index, field))| {
+                        let field_name = field
+                            .ident
+                            .as_ref()
+                            .expect("Named fields must have identifiers")
+                            .clone();
+                        (
+                            info,
+                            field_name,
+                            layout.field_type(variant_ident, index, &field.ty),
+                        )
+                    })
+                    .collect();
+                let field_count = field_infos.len();
+                let deref_attr_fields = deref_fields.len();
+
+                let should_derive_deref = derive_deref(field_count, deref_attr_fields);
+
+                let auto_mark_single_field =
+                    should_derive_deref && deref_attr_fields == 0 && field_count == 1;
+
+                let mut field_tokens: Vec<_> = field_infos
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (info, field_name, field_type))| {
+                        let passthrough_attrs = info.passthrough_attrs.iter();
+                        let mut marker_attrs = Vec::new();
+
+                        // Add event_target attribute for EntityEvent
+                        if target_field == Some(index) {
+                            marker_attrs.push(quote!(#[event_target]));
+                        }
+
+                        if should_derive_deref {
+                            if info.has_deref {
+                                marker_attrs.push(quote!(#[deref]));
+                            }
+                            if info.has_deref_mut {
+                                marker_attrs.push(quote!(#[deref_mut]));
+                            } else if auto_mark_single_field {
+                                marker_attrs.push(quote!(#[deref]));
+                            }
+                        }
+
+                        let field_vis = layout.item_vis(info.vis.as_ref().unwrap_or(&variant_vis));
+                        quote! {
+                            #(#passthrough_attrs)*
+                            #(#marker_attrs)*
+                            #field_vis #field_name: #field_type,
+                        }
+                    })
+                    .collect();
+
+                if injects_entity {
+                    field_tokens.push(quote! {
+                        #[event_target]
+                        #injected_vis entity: #entity_ty,
+                    });
+                }
+                if let Some(phantom_type) = phantom_type.clone() {
+                    field_tokens.push(quote! {
+                        #[doc(hidden)]
+                        #phantom_attrs
+                        pub(crate) _phantom: #phantom_type,
+                    });
+                }
+
+                if should_derive_deref {
+                    quote! {
+                        #[allow(unused_lifetimes, unused_type_parameters)]
+                        #[derive(#event_derive #deref_derive, #deref_mut_derive, #(#derives),*)]
+                        #struct_attrs
+                        #propagate_attr
+                        #struct_vis struct #struct_ident #struct_generics_tokens #where_clause {
+                            #(#field_tokens)*
+                        }
+                    }
+                } else {
+                    quote! {
+                        #[allow(unused_lifetimes, unused_type_parameters)]
+                        #[derive(#event_derive #(#derives),*)]
+                        #struct_attrs
+                        #propagate_attr
+                        #struct_vis struct #struct_ident #struct_generics_tokens #where_clause {
+                            #(#field_tokens)*
+                        }
+                    }
+                }
+            }
+        };
+
+        struct_defs.push(struct_def);
+        // Implemented next to the enum, where the target expression was written
+        if let Some(target) = &computed_target {
+            let ecs = &layout.bevy.ecs;
+            let struct_path = layout.path(&struct_ident);
+            let mut event_generics = generics.clone();
+            event_generics.make_where_clause().predicates.push(
+                syn::parse_quote! { Self: ::core::marker::Send + ::core::marker::Sync + 'static },
+            );
+            let (impl_generics, ty_generics, event_where_clause) = event_generics.split_for_impl();
+            parent_items.push(quote! {
+                impl #impl_generics #ecs::event::Event for #struct_path #ty_generics #event_where_clause {
+                    type Trigger<'a> = #ecs::event::EntityTrigger;
+                }
+
+                impl #impl_generics #ecs::event::EntityEvent for #struct_path #ty_generics #event_where_clause {
+                    #[inline]
+                    fn event_target(&self) -> #ecs::entity::Entity {
+                        #target
+                    }
+                }
+            });
+        }
+        if let Some(shape) = shapes.last() {
+            additional_impls.push(generate_constructors(
+                shape,
+                &layout,
+                &generics,
+                &struct_vis,
+            ));
+        }
+    }
+    if !has_entity_events {
+        for (key, span) in enum_keys.0.iter().filter(|(key, _)| {
+            ["propagate", "auto_propagate", "inject_entity"].contains(&key.as_str())
+        }) {
+            errors.push(syn::Error::new(
+                *span,
+                format!("`{key}` only applies to entity events, and `{enum_name}` has none"),
+            ));
+        }
+    }
+    if let Some(span) = enum_keys.span(&["auto_propagate"]) {
+        errors.check_at(
+            propagate_value.is_some(),
+            span,
+            "`auto_propagate` needs `propagate` next to it",
+        );
+    }
+
+    // The kind enum covers every variant, everything else only the generated ones
+    let kind_shapes = shapes;
+    let shapes: Vec<_> = kind_shapes
+        .iter()
+        .filter(|shape| !shape.skip)
+        .cloned()
+        .collect();
+    let has_skipped = shapes.len() < kind_shapes.len();
+    // Kinds of the generated structs, the derive's own if there are none
+    let mut kinds: Vec<_> = shapes
+        .iter()
+        .flat_map(|shape| [shape.event_kind].into_iter().chain(shape.also))
+        .collect();
+    kinds.sort();
+    kinds.dedup();
+    if kinds.is_empty() {
+        kinds.push(event_kind);
+    }
+    let has_events = kinds.iter().any(|kind| *kind != EventKind::Message);
+
+    let module_doc = match kinds.as_slice() {
+        [EventKind::EntityEvent] => {
+            "Generated module containing entity event types for each enum variant."
+        }
+        [EventKind::Message] => "Generated module containing message types for each enum variant.",
+        [EventKind::Event] => "Generated module containing event types for each enum variant.",
+        _ => "Generated module containing event and message types for each enum variant.",
+    };
+    // The enum's own docs carry over to its module
+    let enum_docs: Vec<_> = input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .collect();
+    let module_doc = if enum_docs.is_empty() {
+        quote! { #[doc = #module_doc] }
+    } else {
+        quote! { #(#enum_docs)* }
+    };
+
+    // Other items of the generated scope, with their `rename_items` key
+    let mut reserved_names = vec![
+        (layout.plugin.to_string(), Some("plugin")),
+        (layout.kind.to_string(), Some("kind")),
+    ];
+    if has_events {
+        reserved_names.push((layout.any.to_string(), Some("any")));
+    }
+    if has_family_io(&generics, &shapes) {
+        reserved_names.extend([
+            (layout.reader.to_string(), Some("reader")),
+            (layout.writer.to_string(), Some("writer")),
+            (layout.write_order.to_string(), Some("write_order")),
+        ]);
+    }
+    if layout.module.is_none() {
+        // Flat structs sit next to the enum itself
+        reserved_names.push((enum_name.to_string(), None));
+    }
+    // Builders sit next to their structs
+    reserved_names.extend(
+        shapes
+            .iter()
+            .filter_map(builder_ident)
+            .map(|builder| (builder.to_string(), None)),
+    );
+    check_struct_names(variants, &layout, &shapes, &reserved_names, &mut errors);
+    errors.finish()?;
+
+    let conversions = generate_conversions(enum_name, &layout, &generics, &shapes);
+    let variant_of = generate_variant_of(enum_name, &layout, &generics, &kind_shapes);
+    let enum_family = generate_enum_family(enum_name, &layout, &generics, &kind_shapes, event_kind);
+    let dispatch = generate_dispatch(
+        enum_name,
+        &layout,
+        &generics,
+        &shapes,
+        event_kind,
+        has_skipped,
+    );
+    let plugin = generate_plugin(&layout, &generics, &shapes);
+    let (family_io_types, family_io_impls) =
+        generate_family_io(enum_name, &layout, &generics, &shapes, has_skipped);
+    let (any_types, any_impls) = generate_any(enum_name, &layout, &generics, &shapes, has_skipped);
+    let (kind_types, kind_impls) = generate_kind(
+        enum_name,
+        &layout,
+        &generics,
+        &kind_shapes,
+        integer_repr(&input.attrs).as_ref(),
+    );
+
+    let scope = generate_scope(
+        &layout,
+        &generics,
+        &shapes,
+        &kind_shapes,
+        integer_repr(&input.attrs).as_ref(),
+        scope_items,
+    );
+
+    let items = quote! {
+        #scope
+        #(#struct_defs)*
+        #(#additional_impls)*
+        #plugin
+        #family_io_types
+        #any_types
+        #kind_types
+    };
+    let module_vis = &layout.vis;
+    let items = match &layout.module {
+        Some(module_name) => {
+            let ecs_import = layout.needs_ecs_import().then(|| {
+                let ecs = &layout.module_bevy.ecs;
+                quote! {
+                    #[allow(unused_imports)]
+                    use #ecs as bevy_ecs;
+                }
+            });
+            quote! {
+                #module_doc
+                #module_vis mod #module_name {
+                    #ecs_import
+
+                    #items
+                }
+            }
+        }
+        None => {
+            let ecs_import = layout.flat_ecs_import(enum_name);
+            quote! {
+                #ecs_import
+                #items
+            }
+        }
+    };
+
+    let expanded = quote! {
+        #items
+
+        #(#parent_items)*
+
+        #conversions
+        #variant_of
+        #enum_family
+        #dispatch
+        #family_io_impls
+        #any_impls
+        #kind_impls
+    };
+
+    Ok(allow_deprecated(expanded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snake_case_conversion() {
+        assert_eq!(to_snake_case("LifeFSM"), "life_fsm");
+        assert_eq!(to_snake_case("PlayerState"), "player_state");
+        assert_eq!(to_snake_case("HTTPServer"), "http_server");
+        assert_eq!(to_snake_case("FSM"), "fsm");
+        assert_eq!(to_snake_case("MyHTTPSConnection"), "my_https_connection");
+    }
+}
@@ -48,9 +48,10 @@
 //! # EnumMessage
 //!
 //! For buffered messages that are written/read between systems using `MessageWriter`/`MessageReader`.
-//! These require registration with `app.add_message::<T>()`.
+//! These require registration with `app.add_message::<T>()`, or all at once with the generated `Plugin`.
 //!
 //! ```rust
+//! use bevy::app::App;
 //! use bevy_enum_event::EnumMessage;
 //!
 //! #[derive(EnumMessage, Clone)]
@@ -61,6 +62,34 @@
 //! }
 //! // Generates: network_message::Connected, network_message::Disconnected, network_message::DataReceived
 //! // Each derives Message and is used with MessageWriter/MessageReader
+//!
+//! App::new().add_plugins(network_message::Plugin);
+//! ```
+//!
+//! The generated `Reader` and `Writer` system params handle the whole family as enum values:
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_enum_event::EnumMessage;
+//!
+//! #[derive(EnumMessage, Clone, Debug)]
+//! enum NetworkMessage {
+//!     Connected(u32),
+//!     Disconnected(u32),
+//! }
+//!
+//! fn send(mut writer: network_message::Writer) {
+//!     writer.write(NetworkMessage::Disconnected(1));
+//!     writer.write(NetworkMessage::Connected(2));
+//! }
+//!
+//! fn receive(mut reader: network_message::Reader) {
+//!     // `read()` groups by variant; this keeps the order of the writes above.
+//!     for message in reader.read_in_write_order() {
+//!         println!("{message:?}");
+//!     }
+//! }
+//! # fn main() {}
 //! ```
 //!
 //! # Deref Feature (default)
@@ -68,6 +97,10 @@
 //! Single-field variants auto-implement `Deref`/`DerefMut`. For multi-field variants,
 //! use `#[enum_event(deref)]`. Disable with `default-features = false`.
 //!
+//! Since features are unified across crates, `#[enum_event(deref = false)]`/`no_deref` and
+//! `#[enum_event(deref)]` on the enum or a variant set the policy locally. Marking a field where
+//! the policy is off, including with the feature off and no local policy, is an error.
+//!
 #![cfg_attr(
     feature = "deref",
     doc = r#"
@@ -90,9 +123,310 @@ assert_eq!(*scored.deref(), 7);
 "#
 )]
 //!
+//! # Conversions
+//!
+//! Each generated struct implements `From<Struct> for Enum`, `TryFrom<Enum> for Struct` and
+//! `PartialEq` in both directions, so values can move freely between the two representations.
+//!
+//! ```rust
+//! use bevy_enum_event::EnumEvent;
+//!
+//! #[derive(EnumEvent, Clone, Debug, PartialEq)]
+//! enum GameEvent {
+//!     Victory(String),
+//!     GameOver,
+//! }
+//!
+//! let event: GameEvent = game_event::Victory("Red".to_string()).into();
+//! assert!(event == game_event::Victory("Red".to_string()));
+//!
+//! // On a mismatch the original value is handed back.
+//! let event = game_event::GameOver::try_from(event).unwrap_err();
+//! let victory = game_event::Victory::try_from(event).unwrap();
+//! assert_eq!(victory.0, "Red");
+//! ```
+//!
+//! # Constructors and Builders
+//!
+//! Every struct gets a `const fn new(...)` and `into_parts()`/`from_parts(...)` tuple conversions.
+//! Named variants also get a `builder()`, whose `build()` is available once every field without
+//! `#[enum_event(default)]` or `#[enum_event(default = expr)]` is set.
+//!
+//! ```rust
+//! use bevy_enum_event::EnumEvent;
+//!
+//! #[derive(EnumEvent, Clone)]
+//! enum GameEvent {
+//!     ScoreChanged {
+//!         team: u32,
+//!         score: u32,
+//!         #[enum_event(default = 1)]
+//!         multiplier: u32,
+//!     },
+//! }
+//!
+//! let scored = game_event::ScoreChanged::builder().score(3).team(1).build();
+//! assert_eq!(scored.into_parts(), (1, 3, 1));
+//! ```
+//!
+//! # Dispatching Enum Values
+//!
+//! Event enums get `trigger(&mut Commands)` and `trigger_world(&mut World)`, message enums get
+//! `write` and `write_world`. Every generated struct is also a `Command`.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_enum_event::EnumEvent;
+//!
+//! #[derive(EnumEvent, Clone)]
+//! enum GameEvent {
+//!     Victory(String),
+//!     GameOver,
+//! }
+//!
+//! fn finish(mut commands: Commands) {
+//!     let event = GameEvent::Victory("Red".to_string());
+//!     event.trigger(&mut commands);
+//!     commands.queue(game_event::GameOver);
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! # Variant Kinds
+//!
+//! Each module has a `Copy` `Kind` enum with one unit variant per source variant, plus
+//! `Kind::ALL` and `Kind::name()`. `kind()` is available on the source enum and every struct.
+//! Explicit discriminants and an integer `#[repr(..)]` carry over.
+//!
+//! ```rust
+//! use bevy_enum_event::EnumEvent;
+//!
+//! #[derive(EnumEvent, Clone)]
+//! enum GameEvent {
+//!     Victory(String),
+//!     GameOver,
+//! }
+//!
+//! assert_eq!(GameEvent::GameOver.kind(), game_event::Kind::GameOver);
+//! assert_eq!(game_event::Victory("Red".to_string()).kind().name(), "Victory");
+//! assert_eq!(game_event::Kind::ALL.len(), 2);
+//! ```
+//!
+//! # Generic Helpers
+//!
+//! Every generated struct implements [`VariantOf`] for its source enum, exposing the variant
+//! name, index and family name as constants. The source enum implements [`EnumFamily`], which
+//! lists the variant names, the module path, the [`EventKind`] and the generated structs as a
+//! tuple type.
+//!
+//! # Observing the Whole Family
+//!
+//! Event and entity event modules include a `Plugin` that re-triggers every variant as the
+//! module's `Any` event, so one observer can handle the whole family.
+//!
+//! ```rust
+//! use bevy::app::App;
+//! use bevy::ecs::observer::On;
+//! use bevy_enum_event::EnumEvent;
+//!
+//! #[derive(EnumEvent, Clone, Debug)]
+//! enum GameEvent {
+//!     Victory(String),
+//!     GameOver,
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins(game_event::Plugin);
+//! app.add_observer(|event: On<game_event::Any>| {
+//!     println!("{:?}", GameEvent::from(event.event().clone()));
+//! });
+//! app.world_mut().trigger(game_event::GameOver);
+//! ```
+//!
+//! # Naming
+//!
+//! `#[enum_event(module = "...")]` names the generated module, `prefix`/`suffix` wrap every
+//! struct name (a bare `prefix` uses the enum name), and a variant's
+//! `#[enum_event(rename = "...")]` sets its struct name outright. Name collisions are reported
+//! at compile time; `#[enum_event(rename_items(kind = "...", any = "..."))]` renames the family
+//! helpers (`plugin`, `kind`, `any`, `reader`, `writer`) to free their names for variants.
+//!
+//! ```rust
+//! use bevy_enum_event::EnumEvent;
+//!
+//! #[derive(EnumEvent, Clone)]
+//! #[enum_event(module = "server_events", suffix = "Event")]
+//! enum HttpServer {
+//!     Started,
+//!     #[enum_event(rename = "Halted")]
+//!     Stopped,
+//! }
+//!
+//! let _ = server_events::StartedEvent;
+//! let _ = server_events::Halted;
+//! ```
+//!
+//! With `#[enum_event(flat)]` no module is generated: the structs sit next to the enum, and
+//! the family helpers are prefixed with the enum name (`GameEventPlugin`, `GameEventKind`,
+//! `GameEventAny`, `GameEventReader`, `GameEventWriter`).
+//!
+//! ```rust
+//! use bevy_enum_event::EnumEvent;
+//!
+//! #[derive(EnumEvent, Clone)]
+//! #[enum_event(flat, suffix = "Event")]
+//! enum GameEvent {
+//!     Victory(String),
+//!     GameOver,
+//! }
+//!
+//! assert_eq!(GameOverEvent.kind(), GameEventKind::GameOver);
+//! ```
+//!
+//! # Conditional Variants
+//!
+//! Disabled variants and fields are removed before the derive runs, so feature-gated variants
+//! behave like feature-gated enum variants. `VARIANT_NAMES`, `Kind::ALL` and `VariantOf::INDEX`
+//! only count the variants that are compiled in.
+//!
+//! # Skipping Variants
+//!
+//! `#[enum_event(skip)]` on a variant generates nothing for it except its `Kind`; it still counts
+//! in `VARIANT_NAMES`, `VARIANT_COUNT` and `VariantOf::INDEX`, which follow `Kind::ALL`. Dispatch
+//! methods and `Writer::write` then return `Result<(), Enum>`, handing skipped values back, and
+//! `Any` converts from the enum with `TryFrom`.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_enum_event::EnumEvent;
+//!
+//! #[derive(EnumEvent, Clone, Debug)]
+//! enum GameEvent {
+//!     Victory(String),
+//!     #[enum_event(skip)]
+//!     Reserved,
+//! }
+//!
+//! # fn main() {
+//! let mut world = World::new();
+//! assert!(GameEvent::Victory("Red".to_string()).trigger_world(&mut world).is_ok());
+//! assert!(GameEvent::Reserved.trigger_world(&mut world).is_err());
+//! # }
+//! ```
+//!
+//! # Mixing Kinds
+//!
+//! `#[enum_event(kind = message | event | entity)]` on a variant overrides the kind given by the
+//! derive. Event variants are then dispatched with `trigger` and message variants with `write`,
+//! each returning `Result<(), Enum>` that hands values of the other kind back.
+//! `also = message` (or `also = event`/`entity` on messages) makes a struct both, for events
+//! that need observer delivery as well as buffered reading.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_enum_event::EnumEvent;
+//!
+//! #[derive(EnumEvent, Clone, Debug)]
+//! enum CombatEvent {
+//!     RoundStarted(u32),
+//!     #[enum_event(kind = message)]
+//!     DamageDealt { amount: u32 },
+//! }
+//!
+//! # fn main() {
+//! let mut app = App::new();
+//! app.add_plugins(combat_event::Plugin);
+//! CombatEvent::RoundStarted(1).trigger_world(app.world_mut()).unwrap();
+//! CombatEvent::DamageDealt { amount: 3 }.write_world(app.world_mut()).unwrap();
+//! assert_eq!(app.world().resource::<Messages<combat_event::DamageDealt>>().len(), 1);
+//! # }
+//! ```
+//!
+//! # Attributes and Docs
+//!
+//! Variant doc comments, `#[deprecated]`, lint attributes and `#[cfg]` are copied onto the
+//! generated structs, and the enum's doc comment onto the generated module. Other variant
+//! attributes, like the helpers of the enum's derives, are only copied when listed in
+//! `#[enum_event(forward_attrs(...))]` on the enum or the variant, and `exclude_attrs(...)`
+//! leaves attributes out; `#[default]` is never copied.
+//!
+//! # Derives
+//!
+//! Generated structs derive `Clone` and `Debug`, plus `Copy` and `Default` for unit variants.
+//! `#[enum_event(derive(...))]` on the enum or a variant adds derives, `no_clone` and
+//! `no_debug` drop the defaults, and `forward_derives` copies the enum's derives from a
+//! separate `#[derive(...)]` attribute placed below it. The generated `Plugin` registers the
+//! structs that derive `Reflect` with the type registry.
+//!
+//! ```rust
+//! use bevy_enum_event::EnumEvent;
+//!
+//! #[derive(EnumEvent)]
+//! #[enum_event(no_clone, no_debug)]
+//! enum TaskEvent {
+//!     Spawned(Box<dyn Fn() -> u32 + Send + Sync>),
+//!     #[enum_event(derive(PartialEq))]
+//!     Finished,
+//! }
+//!
+//! assert!(task_event::Finished == task_event::Finished);
+//! ```
+//!
+//! # Visibility
+//!
+//! The generated module, structs and fields inherit the enum's visibility. Use
+//! `#[enum_event(vis = "...")]` on the enum, a variant or a field to override it; an empty
+//! string keeps the item private to the enum's module.
+//!
+//! ```rust
+//! mod health {
+//!     use bevy_enum_event::EnumEvent;
+//!
+//!     #[derive(Clone, Debug)]
+//!     pub(crate) struct SourceId(pub(crate) u64);
+//!
+//!     #[derive(EnumEvent, Clone, Debug)]
+//!     pub(crate) enum HealthEvent {
+//!         Changed {
+//!             amount: i32,
+//!             #[enum_event(vis = "")]
+//!             source: SourceId,
+//!         },
+//!     }
+//!
+//!     pub(crate) fn changed(amount: i32) -> health_event::Changed {
+//!         health_event::Changed { amount, source: SourceId(0) }
+//!     }
+//! }
+//!
+//! // `amount` is readable here, `source` only inside `health`
+//! assert_eq!(health::changed(5).amount, 5);
+//! ```
+//!
+//! # Bevy Crate Path
+//!
+//! The generated code uses `bevy`, or `bevy_ecs` when the calling crate depends on `bevy_ecs`
+//! but not on `bevy`. `#[enum_event(crate = path)]` overrides this, for example with
+//! `crate = my_engine::bevy` for a re-export or `crate = bevy_ecs` for the ECS crate alone. Without
+//! `bevy_app` no `Plugin` is generated.
+//!
+//! Generated modules import nothing from the enum's scope and name Bevy items by absolute
+//! paths. Field types, bounds and expressions go through a hidden `__scope` module, the one
+//! place that glob-imports the enum's scope, so they resolve as they do next to the enum. The
+//! glob can't see inside a function body, so enums declared in one should use `flat` to name the
+//! function's own items.
+//!
+//! # Attribute Errors
+//!
+//! `#[enum_event(...)]` keys are validated on the enum, variants and fields: misspellings suggest
+//! the nearest valid key, and settings that would have no effect, like `propagate` on a
+//! non-entity event or `default` on a variant without a builder, are compile errors.
+//!
 //! # EnumEntityEvent
 //!
-//! Entity-targeted events. Requires named fields with `entity: Entity` or `#[enum_event(target)]`.
+//! Entity-targeted events. Each variant needs an `entity: Entity` field, a field marked
+//! `#[enum_event(target)]` or a single positional `Entity`, unless the enum has
+//! `#[enum_event(inject_entity)]`.
 //!
 //! ```rust
 //! use bevy::prelude::*;
@@ -102,6 +436,7 @@ assert_eq!(*scored.deref(), 7);
 //! enum PlayerEvent {
 //!     Damaged { entity: Entity, amount: f32 },
 //! }
+//! # fn main() {}
 //! ```
 //!
 //! ## Custom Target
@@ -117,7 +452,59 @@ assert_eq!(*scored.deref(), 7);
 //!         attacker: Entity,
 //!         defender: Entity,
 //!     },
+//!     Died(Entity),
+//!     Healed(u32, #[enum_event(target)] Entity),
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! ## Computed Targets
+//!
+//! `#[enum_event(target = expr)]` on a variant computes the target from `&self`, with a leading
+//! field name standing for `self.field`. These variants can't use `propagate`.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_enum_event::EnumEntityEvent;
+//!
+//! #[derive(Clone, Copy, Debug)]
+//! struct HitInfo {
+//!     victim: Entity,
+//! }
+//!
+//! #[derive(EnumEntityEvent, Clone, Copy)]
+//! enum CombatEvent {
+//!     #[enum_event(target = hit.victim)]
+//!     Hit { hit: HitInfo },
+//!     #[enum_event(target = owner.unwrap_or(Entity::PLACEHOLDER))]
+//!     Looted { owner: Option<Entity> },
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! ## Injected Targets
+//!
+//! `#[enum_event(inject_entity)]` adds an `entity: Entity` field to the structs of target-less
+//! variants. Their constructors take it first, and since the enum value has no entity to give,
+//! dispatching it hands such variants back as an error.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_enum_event::EnumEntityEvent;
+//!
+//! #[derive(EnumEntityEvent, Clone)]
+//! #[enum_event(inject_entity)]
+//! enum PlayerEvent {
+//!     Selected,
+//!     Renamed(String),
 //! }
+//!
+//! # fn main() {
+//! let mut world = World::new();
+//! let player = world.spawn_empty().id();
+//! world.trigger(player_event::Renamed::new(player, "Ada".to_string()));
+//! assert!(PlayerEvent::Selected.trigger_world(&mut world).is_err());
+//! # }
 //! ```
 //!
 //! ## Propagation
@@ -143,818 +530,87 @@ assert_eq!(*scored.deref(), 7);
 //! enum CustomEvent {
 //!     Action { entity: Entity },
 //! }
+//! # fn main() {}
 //! ```
 
-use proc_macro::TokenStream;
-use quote::quote;
-use std::collections::HashSet;
-use syn::{parse_macro_input, visit::Visit, Attribute, Data, DeriveInput, Fields};
-
-/// Converts `PascalCase` or `camelCase` to `snake_case`.
-///
-/// Handles acronyms gracefully: `FSMState` → `fsm_state`, `HTTPServer` → `http_server`
-fn to_snake_case(s: &str) -> String {
-    let mut result = String::new();
-    let chars: Vec<char> = s.chars().collect();
-
-    for (i, &ch) in chars.iter().enumerate() {
-        if ch.is_uppercase() {
-            let is_first = i == 0;
-            let prev_is_lower = i > 0 && chars[i - 1].is_lowercase();
-            let next_is_lower = i + 1 < chars.len() && chars[i + 1].is_lowercase();
-
-            // Add underscore if:
-            // 1. Previous char is lowercase (camelCase -> snake_case)
-            // 2. This is uppercase, next is lowercase, and we're not first (handles acronyms)
-            if !is_first && (prev_is_lower || next_is_lower) {
-                result.push('_');
-            }
-
-            result.push(ch.to_lowercase().next().unwrap());
-        } else {
-            result.push(ch);
-        }
-    }
-    result
-}
-
-struct GenericsUsageCollector<'a> {
-    type_names: &'a [String],
-    lifetime_names: &'a [String],
-    pub used_types: HashSet<String>,
-    pub used_lifetimes: HashSet<String>,
-}
-
-impl<'a> GenericsUsageCollector<'a> {
-    fn new(type_names: &'a [String], lifetime_names: &'a [String]) -> Self {
-        Self {
-            type_names,
-            lifetime_names,
-            used_types: HashSet::new(),
-            used_lifetimes: HashSet::new(),
-        }
-    }
-}
-
-impl<'ast> Visit<'ast> for GenericsUsageCollector<'_> {
-    fn visit_type_path(&mut self, type_path: &'ast syn::TypePath) {
-        if type_path.qself.is_none() {
-            if let Some(ident) = type_path.path.get_ident() {
-                let ident_str = ident.to_string();
-                if self.type_names.iter().any(|name| name == &ident_str) {
-                    self.used_types.insert(ident_str);
-                }
-            }
-        }
-        syn::visit::visit_type_path(self, type_path);
-    }
-
-    fn visit_lifetime(&mut self, lifetime: &'ast syn::Lifetime) {
-        let ident_str = lifetime.ident.to_string();
-        if self.lifetime_names.iter().any(|name| name == &ident_str) {
-            self.used_lifetimes.insert(ident_str);
-        }
-        syn::visit::visit_lifetime(self, lifetime);
-    }
-}
-
-fn path_ends_with_ident(path: &syn::Path, ident: &str) -> bool {
-    path.segments
-        .last()
-        .is_some_and(|segment| segment.ident == ident)
-}
-
-#[derive(Default)]
-struct FieldAttrInfo {
-    passthrough_attrs: Vec<Attribute>,
-    has_deref: bool,
-    has_deref_mut: bool,
-    is_event_target: bool,
-}
-
-#[derive(Default)]
-struct VariantAttrInfo {
-    propagate_value: Option<proc_macro2::TokenStream>,
-    has_auto_propagate: bool,
-}
+pub use bevy_enum_event_derive::{EnumEntityEvent, EnumEvent, EnumMessage};
 
-fn analyze_field_attrs(attrs: &[Attribute]) -> FieldAttrInfo {
-    let mut info = FieldAttrInfo::default();
-
-    for attr in attrs {
-        if path_ends_with_ident(attr.path(), "enum_event") {
-            if let Err(err) = attr.parse_nested_meta(|meta| {
-                if path_ends_with_ident(&meta.path, "deref") {
-                    info.has_deref = true;
-                } else if path_ends_with_ident(&meta.path, "deref_mut") {
-                    info.has_deref_mut = true;
-                    info.has_deref = true;
-                } else if path_ends_with_ident(&meta.path, "target") {
-                    info.is_event_target = true;
-                }
-                Ok(())
-            }) {
-                panic!("bevy_enum_event: failed to parse #[enum_event(...)] attribute: {err}");
-            }
-        } else if path_ends_with_ident(attr.path(), "event_target") {
-            info.is_event_target = true;
-        } else if path_ends_with_ident(attr.path(), "deref") {
-            info.has_deref = true;
-        } else if path_ends_with_ident(attr.path(), "deref_mut") {
-            info.has_deref_mut = true;
-            info.has_deref = true;
-        } else {
-            info.passthrough_attrs.push(attr.clone());
-        }
-    }
-
-    info
-}
-
-fn analyze_variant_attrs(attrs: &[Attribute]) -> VariantAttrInfo {
-    let mut info = VariantAttrInfo::default();
-
-    for attr in attrs {
-        if path_ends_with_ident(attr.path(), "enum_event") {
-            if let Err(err) = attr.parse_nested_meta(|meta| {
-                if path_ends_with_ident(&meta.path, "auto_propagate") {
-                    info.has_auto_propagate = true;
-                    Ok(())
-                } else if path_ends_with_ident(&meta.path, "propagate") {
-                    if meta.input.peek(syn::Token![=]) {
-                        // Parse: propagate = <value>
-                        meta.input.parse::<syn::Token![=]>()?;
-                        let tokens: proc_macro2::TokenStream = meta.input.parse()?;
-                        info.propagate_value = Some(tokens);
-                    } else {
-                        // Just: propagate (no value, uses default)
-                        info.propagate_value = Some(quote! {});
-                    }
-                    Ok(())
-                } else {
-                    // Unknown attributes on variants are just ignored (could be other macro's attributes)
-                    Ok(())
-                }
-            }) {
-                panic!("EnumMessage: failed to parse variant #[enum_event(...)] attribute: {err}");
-            }
-        }
-    }
-
-    info
-}
-
-/// Generates Bevy `Event` types from enum variants for observer-based events.
+/// Links a generated struct to the enum it was generated from.
 ///
-/// Creates a snake_case module with one event struct per variant.
-/// These events are triggered via `world.trigger()` and handled by observers.
+/// Implemented for every struct in a generated module, so helpers can be written once for any
+/// family:
 ///
 /// ```rust
-/// use bevy_enum_event::EnumEvent;
+/// use bevy_enum_event::{EnumEvent, VariantOf};
 ///
 /// #[derive(EnumEvent, Clone)]
-/// enum Action {
-///     Jump,
-///     Run(f32),
-///     Attack { damage: i32, critical: bool },
+/// enum GameEvent {
+///     Victory(String),
+///     GameOver,
 /// }
-/// // Generates: action::Jump, action::Run, action::Attack
-/// // Each struct derives Event for use with triggers/observers
-/// ```
-#[proc_macro_derive(EnumEvent, attributes(enum_event, deref, deref_mut))]
-pub fn derive_enum_events(input: TokenStream) -> TokenStream {
-    derive_enum_event_impl(input, EventKind::Event)
-}
-
-/// Generates Bevy `Message` types from enum variants for buffered message passing.
-///
-/// Creates a snake_case module with one message struct per variant.
-/// These messages are written via `MessageWriter` and read via `MessageReader`.
-/// Each generated type must be registered with `app.add_message::<T>()`.
-///
-/// ```rust
-/// use bevy_enum_event::EnumMessage;
 ///
-/// #[derive(EnumMessage, Clone)]
-/// enum NetworkMessage {
-///     Connected(String),
-///     Disconnected { reason: String },
-///     DataReceived { data: Vec<u8> },
+/// fn describe<V: VariantOf<E>, E>(_variant: &V) -> String {
+///     format!("{}::{} (#{})", V::FAMILY, V::NAME, V::INDEX)
 /// }
-/// // Generates: network_message::Connected, network_message::Disconnected, network_message::DataReceived
-/// // Each struct derives Message for use with MessageWriter/MessageReader
+///
+/// assert_eq!(describe(&game_event::GameOver), "GameEvent::GameOver (#1)");
 /// ```
-#[proc_macro_derive(EnumMessage, attributes(enum_event, deref, deref_mut))]
-pub fn derive_enum_messages(input: TokenStream) -> TokenStream {
-    derive_enum_event_impl(input, EventKind::Message)
+pub trait VariantOf<E>: Sized {
+    /// Name of the source variant, e.g. `"Victory"`.
+    const NAME: &'static str;
+    /// Position of the variant in the source enum, starting at zero.
+    const INDEX: usize;
+    /// Name of the source enum, e.g. `"GameEvent"`.
+    const FAMILY: &'static str;
+
+    /// Moves the payload back into the source enum.
+    fn into_family(self) -> E;
+}
+
+/// Which Bevy trait the structs of a family implement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    /// Observer-based global events, from `#[derive(EnumEvent)]`.
+    Event,
+    /// Buffered messages, from `#[derive(EnumMessage)]`.
+    Message,
+    /// Entity-targeted observer events, from `#[derive(EnumEntityEvent)]`.
+    EntityEvent,
 }
 
-/// Generates Bevy `EntityEvent` types from enum variants.
+/// Describes a source enum and the module generated from it.
 ///
-/// Requires named fields with `entity: Entity` or `#[enum_event(target)]`.
+/// Implemented for every enum deriving one of the macros, so plugins, test harnesses and
+/// exporters can work over any family:
 ///
 /// ```rust
-/// use bevy::prelude::*;
-/// use bevy_enum_event::EnumEntityEvent;
+/// use bevy_enum_event::{EnumEvent, EnumFamily, EventKind};
 ///
-/// #[derive(EnumEntityEvent, Clone, Copy)]
-/// enum PlayerEvent {
-///     Spawned { entity: Entity },
-///     Damaged { entity: Entity, amount: f32 },
+/// #[derive(EnumEvent, Clone)]
+/// enum GameEvent {
+///     Victory(String),
+///     GameOver,
 /// }
-/// ```
-///
-/// # Propagation
 ///
-/// ```rust
-/// use bevy::prelude::*;
-/// use bevy_enum_event::EnumEntityEvent;
-///
-/// #[derive(EnumEntityEvent, Clone, Copy)]
-/// #[enum_event(propagate)]
-/// enum UiEvent {
-///     Click { entity: Entity },
+/// fn summary<E: EnumFamily>() -> String {
+///     format!("{}: {}", E::MODULE_PATH, E::VARIANT_NAMES.join(", "))
 /// }
 ///
-/// #[derive(EnumEntityEvent, Clone, Copy)]
-/// #[enum_event(auto_propagate, propagate)]
-/// enum SystemEvent {
-///     Update { entity: Entity },
-/// }
+/// assert!(summary::<GameEvent>().ends_with("game_event: Victory, GameOver"));
+/// assert_eq!(GameEvent::EVENT_KIND, EventKind::Event);
 /// ```
-#[proc_macro_derive(
-    EnumEntityEvent,
-    attributes(enum_event, event_target, deref, deref_mut)
-)]
-pub fn derive_enum_entity_events(input: TokenStream) -> TokenStream {
-    derive_enum_event_impl(input, EventKind::EntityEvent)
-}
-
-/// Specifies which kind of Bevy event/message to generate.
-#[derive(Clone, Copy, PartialEq, Eq)]
-enum EventKind {
-    /// Observer-based global events (`#[derive(Event)]`)
-    Event,
-    /// Buffered messages (`#[derive(Message)]`)
-    Message,
-    /// Entity-targeted observer events (`#[derive(EntityEvent)]`)
-    EntityEvent,
-}
-
-#[allow(clippy::too_many_lines)]
-fn derive_enum_event_impl(input: TokenStream, event_kind: EventKind) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-    let enum_name = &input.ident;
-    let is_entity_event = event_kind == EventKind::EntityEvent;
-
-    // Check for propagate and auto_propagate attributes on the enum
-    // Can be: #[enum_event(propagate)]
-    //         #[enum_event(propagate = &'static RelType)]
-    //         #[enum_event(auto_propagate, propagate = &'static RelType)]
-    let mut propagate_value: Option<proc_macro2::TokenStream> = None;
-    let mut has_auto_propagate = false;
-
-    for attr in &input.attrs {
-        if path_ends_with_ident(attr.path(), "enum_event") {
-            attr.parse_nested_meta(|meta| {
-                if path_ends_with_ident(&meta.path, "auto_propagate") {
-                    has_auto_propagate = true;
-                    Ok(())
-                } else if path_ends_with_ident(&meta.path, "propagate") {
-                    if meta.input.peek(syn::Token![=]) {
-                        // Parse: propagate = <value>
-                        // Capture the remaining tokens as-is without parsing
-                        meta.input.parse::<syn::Token![=]>()?;
-                        // Parse the rest of the input as raw tokens
-                        let tokens: proc_macro2::TokenStream = meta.input.parse()?;
-                        propagate_value = Some(tokens);
-                    } else {
-                        // Just: propagate (no value, uses default)
-                        propagate_value = Some(quote! {});
-                    }
-                    Ok(())
-                } else {
-                    Err(meta.error("unknown enum_event attribute"))
-                }
-            })
-            .unwrap_or_else(|e| panic!("Failed to parse enum_event attribute: {e}"));
-        }
-    }
-
-    // Extract variants from enum
-    let variants = match &input.data {
-        Data::Enum(data_enum) => &data_enum.variants,
-        _ => panic!("bevy_enum_event: macros can only be derived for enums"),
-    };
-
-    // Convert EnumName to snake_case for module name
-    let module_name_str = to_snake_case(&enum_name.to_string());
-    let module_name = syn::Ident::new(&module_name_str, enum_name.span());
-
-    #[allow(clippy::items_after_statements)]
-    fn adjust_propagate_type_for_module(ty: &mut syn::Type) {
-        fn adjust_path(path: &mut syn::TypePath) {
-            if path.path.leading_colon.is_some() {
-                return;
-            }
-
-            if let Some(first) = path.path.segments.first() {
-                let ident = &first.ident;
-                let starts_with_crate = ident == "crate";
-                let starts_with_super = ident == "super";
-                let starts_with_self = ident == "self";
-
-                if starts_with_crate || starts_with_super || starts_with_self {
-                    return;
-                }
-            }
-
-            path.path.segments.insert(0, syn::parse_quote!(super));
-        }
-
-        match ty {
-            syn::Type::Reference(ref mut reference) => {
-                adjust_propagate_type_for_module(&mut reference.elem);
-            }
-            syn::Type::Path(ref mut type_path) => adjust_path(type_path),
-            _ => {}
-        }
-    }
-
-    let generics = input.generics.clone();
-    let struct_generics = if generics.params.is_empty() {
-        quote! {}
-    } else {
-        let params = generics.params.iter();
-        quote! { <#(#params),*> }
-    };
-    let where_clause = generics.where_clause.as_ref();
-    let type_params: Vec<(String, syn::Ident)> = generics
-        .type_params()
-        .map(|param| (param.ident.to_string(), param.ident.clone()))
-        .collect();
-    let lifetime_params: Vec<(String, syn::Lifetime)> = generics
-        .lifetimes()
-        .map(|param| {
-            let lt = param.lifetime.clone();
-            (lt.ident.to_string(), lt)
-        })
-        .collect();
-    let type_param_names: Vec<String> = type_params.iter().map(|(name, _)| name.clone()).collect();
-    let lifetime_param_names: Vec<String> = lifetime_params
-        .iter()
-        .map(|(name, _)| name.clone())
-        .collect();
-
-    // Generate struct definitions for each variant
-    let mut struct_defs = Vec::new();
-    let mut additional_impls = Vec::new();
-    let mut uses_deref_derives = false;
-
-    for variant in variants {
-        let variant_ident = &variant.ident;
-        let struct_generics_tokens = struct_generics.clone();
-
-        // Parse variant-level propagate attributes
-        let variant_attr_info = analyze_variant_attrs(&variant.attrs);
-
-        // Determine propagate settings for this variant:
-        // - If variant has propagate settings, use those (override enum-level)
-        // - Otherwise, use enum-level settings
-        let variant_has_propagate = variant_attr_info.propagate_value.is_some();
-        let variant_propagate_value = if variant_has_propagate {
-            variant_attr_info.propagate_value.clone()
-        } else {
-            propagate_value.clone()
-        };
-        let variant_auto_propagate = if variant_has_propagate {
-            variant_attr_info.has_auto_propagate
-        } else {
-            has_auto_propagate
-        };
-
-        let mut usage_collector =
-            GenericsUsageCollector::new(&type_param_names, &lifetime_param_names);
-        for field in &variant.fields {
-            usage_collector.visit_type(&field.ty);
-        }
-        let unused_type_params: Vec<_> = type_params
-            .iter()
-            .filter(|(name, _)| !usage_collector.used_types.contains(name))
-            .map(|(_, ident)| ident.clone())
-            .collect();
-        let unused_lifetimes: Vec<_> = lifetime_params
-            .iter()
-            .filter(|(name, _)| !usage_collector.used_lifetimes.contains(name))
-            .map(|(_, lifetime)| lifetime.clone())
-            .collect();
-        let phantom_entries: Vec<_> = unused_type_params
-            .iter()
-            .map(|ident| quote! { #ident })
-            .chain(unused_lifetimes.iter().map(|lt| {
-                quote! { &#lt () }
-            }))
-            .collect();
-        let phantom_type = if phantom_entries.is_empty() {
-            None
-        } else {
-            Some(quote! { ::core::marker::PhantomData<(#(#phantom_entries ,)*)> })
-        };
-        let mut extra_impl = None;
-
-        // For EntityEvent, check if the variant has an entity field
-        let has_entity_field = if is_entity_event {
-            match &variant.fields {
-                Fields::Named(fields) => {
-                    // Check for entity field or marked target field
-                    let target_fields: Vec<_> = fields
-                        .named
-                        .iter()
-                        .filter(|field| {
-                            let info = analyze_field_attrs(&field.attrs);
-                            info.is_event_target
-                                || field.ident.as_ref().is_some_and(|id| id == "entity")
-                        })
-                        .collect();
-
-                    assert!(target_fields.len() <= 1,
-                            "EnumEntityEvent: variant `{variant_ident}` has multiple fields marked as event target; only one field can be the target"
-                        );
-
-                    !target_fields.is_empty()
-                }
-                Fields::Unnamed(_) | Fields::Unit => false,
-            }
-        } else {
-            false
-        };
-
-        assert!(!is_entity_event || has_entity_field,
-                "EnumEntityEvent: variant `{variant_ident}` must have an `entity: Entity` field or a field marked with #[enum_event(target)]"
-            );
-
-        let event_derive = match event_kind {
-            EventKind::EntityEvent => quote! { EntityEvent },
-            EventKind::Message => quote! { Message },
-            EventKind::Event => quote! { Event },
-        };
-
-        let struct_doc = match event_kind {
-            EventKind::EntityEvent => "Entity event type corresponding to the enum variant.",
-            EventKind::Message => "Message type corresponding to the enum variant.",
-            EventKind::Event => "Event type corresponding to the enum variant.",
-        };
-
-        let struct_def = match &variant.fields {
-            Fields::Unit => {
-                // Unit variants cannot be EntityEvents
-                assert!(!is_entity_event,
-                        "EnumEntityEvent: variant `{variant_ident}` is a unit variant; entity events must have at least an entity field"
-                    );
-
-                if let Some(phantom_type) = phantom_type.clone() {
-                    let (impl_generics_impl, ty_generics_impl, where_clause_impl) =
-                        generics.split_for_impl();
-                    extra_impl = Some(quote! {
-                        impl #impl_generics_impl #variant_ident #ty_generics_impl #where_clause_impl {
-                            #[inline]
-                            pub const fn new() -> Self {
-                                Self {
-                                    _phantom: ::core::marker::PhantomData,
-                                }
-                            }
-                        }
-                    });
-                    quote! {
-                        #[doc = #struct_doc]
-                        #[allow(unused_lifetimes, unused_type_parameters)]
-                        #[derive(#event_derive, Clone, Copy, Debug, Default)]
-                        pub struct #variant_ident #struct_generics_tokens #where_clause {
-                            #[doc(hidden)]
-                            pub(crate) _phantom: #phantom_type,
-                        }
-                    }
-                } else {
-                    quote! {
-                        #[doc = #struct_doc]
-                        #[allow(unused_lifetimes, unused_type_parameters)]
-                        #[derive(#event_derive, Clone, Copy, Debug, Default)]
-                        pub struct #variant_ident #struct_generics_tokens #where_clause;
-                    }
-                }
-            }
-            Fields::Unnamed(fields) => {
-                // Tuple variants cannot be EntityEvents
-                assert!(!is_entity_event,
-                        "EnumEntityEvent: variant `{variant_ident}` is a tuple variant; entity events must use named fields with an `entity: Entity` field"
-                    );
-
-                let struct_generics_tokens = struct_generics_tokens.clone();
-                let field_infos: Vec<_> = fields
-                    .unnamed
-                    .iter()
-                    .map(|field| {
-                        let info = analyze_field_attrs(&field.attrs);
-                        (info, &field.ty)
-                    })
-                    .collect();
-                let field_count = field_infos.len();
-                let deref_attr_fields = field_infos
-                    .iter()
-                    .filter(|(info, _)| info.has_deref)
-                    .count();
-
-                assert!(!(field_count > 1 && deref_attr_fields > 1),
-                        "bevy_enum_event: variant `{variant_ident}` has multiple fields marked for deref (e.g., #[enum_event(deref)]); only one field can be dereferenced"
-                    );
-
-                let should_derive_deref =
-                    cfg!(feature = "deref") && (field_count == 1 || deref_attr_fields == 1);
-
-                let mut field_tokens: Vec<_> = field_infos
-                    .iter()
-                    .map(|(info, ty)| {
-                        let passthrough_attrs = info.passthrough_attrs.iter();
-                        let mut marker_attrs = Vec::new();
-
-                        if should_derive_deref {
-                            if info.has_deref {
-                                marker_attrs.push(quote!(#[deref]));
-                            }
-                            if info.has_deref_mut {
-                                marker_attrs.push(quote!(#[deref_mut]));
-                            }
-                        }
-
-                        quote! {
-                            #(#passthrough_attrs)*
-                            #(#marker_attrs)*
-                            pub #ty
-                        }
-                    })
-                    .collect();
-
-                if let Some(phantom_type) = phantom_type.clone() {
-                    field_tokens.push(quote! {
-                        #[doc(hidden)]
-                        pub(crate) #phantom_type
-                    });
-
-                    let (impl_generics_impl, ty_generics_impl, where_clause_impl) =
-                        generics.split_for_impl();
-                    let arg_idents: Vec<_> = (0..field_infos.len())
-                        .map(|index| {
-                            syn::Ident::new(&format!("__arg{index}"), variant_ident.span())
-                        })
-                        .collect();
-                    let arg_defs: Vec<_> = field_infos
-                        .iter()
-                        .enumerate()
-                        .map(|(idx, (_, ty))| {
-                            let ident = &arg_idents[idx];
-                            quote! { #ident: #ty }
-                        })
-                        .collect();
-                    let arg_values = arg_idents.iter();
-
-                    extra_impl = Some(quote! {
-                        impl #impl_generics_impl #variant_ident #ty_generics_impl #where_clause_impl {
-                            #[inline]
-                            pub fn new(#(#arg_defs),*) -> Self {
-                                Self(#(#arg_values),*, ::core::marker::PhantomData)
-                            }
-                        }
-                    });
-                }
-
-                if should_derive_deref {
-                    uses_deref_derives = true;
-                    quote! {
-                        #[doc = #struct_doc]
-                        #[allow(unused_lifetimes, unused_type_parameters)]
-                        #[derive(#event_derive, Deref, DerefMut, Clone, Debug)]
-                        pub struct #variant_ident #struct_generics_tokens(#(#field_tokens),*) #where_clause;
-                    }
-                } else {
-                    quote! {
-                        #[doc = #struct_doc]
-                        #[allow(unused_lifetimes, unused_type_parameters)]
-                        #[derive(#event_derive, Clone, Debug)]
-                        pub struct #variant_ident #struct_generics_tokens(#(#field_tokens),*) #where_clause;
-                    }
-                }
-            }
-            Fields::Named(fields) => {
-                let struct_generics_tokens = struct_generics_tokens.clone();
-                let field_infos: Vec<_> = fields
-                    .named
-                    .iter()
-                    .map(|field| {
-                        let info = analyze_field_attrs(&field.attrs);
-                        let field_name = field
-                            .ident
-                            .as_ref()
-                            .expect("Named fields must have identifiers")
-                            .clone();
-                        (info, field_name, &field.ty)
-                    })
-                    .collect();
-                let field_count = field_infos.len();
-                let deref_attr_fields = field_infos
-                    .iter()
-                    .filter(|(info, _, _)| info.has_deref)
-                    .count();
-
-                assert!(!(field_count > 1 && deref_attr_fields > 1),
-                        "bevy_enum_event: variant `{variant_ident}` has multiple fields marked for deref (e.g., #[enum_event(deref)]); only one field can be dereferenced"
-                    );
-
-                let should_derive_deref =
-                    cfg!(feature = "deref") && (field_count == 1 || deref_attr_fields == 1);
-
-                let auto_mark_single_field =
-                    should_derive_deref && deref_attr_fields == 0 && field_count == 1;
-
-                let mut field_tokens: Vec<_> = field_infos
-                    .iter()
-                    .map(|(info, field_name, field_type)| {
-                        let passthrough_attrs = info.passthrough_attrs.iter();
-                        let mut marker_attrs = Vec::new();
-
-                        // Add event_target attribute for EntityEvent
-                        if is_entity_event && (info.is_event_target || field_name == "entity") {
-                            marker_attrs.push(quote!(#[event_target]));
-                        }
-
-                        if should_derive_deref {
-                            if info.has_deref {
-                                marker_attrs.push(quote!(#[deref]));
-                            }
-                            if info.has_deref_mut {
-                                marker_attrs.push(quote!(#[deref_mut]));
-                            } else if auto_mark_single_field {
-                                marker_attrs.push(quote!(#[deref]));
-                            }
-                        } else if auto_mark_single_field {
-                            marker_attrs.push(quote!(#[deref]));
-                        }
-
-                        quote! {
-                            #(#passthrough_attrs)*
-                            #(#marker_attrs)*
-                            pub #field_name: #field_type,
-                        }
-                    })
-                    .collect();
-
-                if let Some(phantom_type) = phantom_type.clone() {
-                    field_tokens.push(quote! {
-                        #[doc(hidden)]
-                        pub(crate) _phantom: #phantom_type,
-                    });
-
-                    let (impl_generics_impl, ty_generics_impl, where_clause_impl) =
-                        generics.split_for_impl();
-                    let arg_defs: Vec<_> = field_infos
-                        .iter()
-                        .map(|(_, field_name, field_type)| {
-                            quote! { #field_name: #field_type }
-                        })
-                        .collect();
-                    let field_names: Vec<_> = field_infos
-                        .iter()
-                        .map(|(_, field_name, _)| field_name)
-                        .collect();
-
-                    extra_impl = Some(quote! {
-                        impl #impl_generics_impl #variant_ident #ty_generics_impl #where_clause_impl {
-                            #[inline]
-                            pub fn new(#(#arg_defs),*) -> Self {
-                                Self {
-                                    #(#field_names),*,
-                                    _phantom: ::core::marker::PhantomData,
-                                }
-                            }
-                        }
-                    });
-                }
-
-                // Note: We accept #[enum_event(propagate)] on the enum, but generate #[entity_event(propagate)]
-                // on the struct because that's what Bevy's EntityEvent derive expects
-                // Generate variant-specific propagate attributes
-                let propagate_attr = if is_entity_event && variant_propagate_value.is_some() {
-                    match variant_propagate_value.clone() {
-                        Some(tokens) if tokens.is_empty() => {
-                            if variant_auto_propagate {
-                                quote! { #[entity_event(auto_propagate, propagate)] }
-                            } else {
-                                quote! { #[entity_event(propagate)] }
-                            }
-                        }
-                        Some(tokens) => {
-                            let adjusted_tokens =
-                                if let Ok(mut ty) = syn::parse2::<syn::Type>(tokens.clone()) {
-                                    adjust_propagate_type_for_module(&mut ty);
-                                    quote! { #ty }
-                                } else {
-                                    quote! { #tokens }
-                                };
-
-                            if variant_auto_propagate {
-                                quote! { #[entity_event(auto_propagate, propagate = #adjusted_tokens)] }
-                            } else {
-                                quote! { #[entity_event(propagate = #adjusted_tokens)] }
-                            }
-                        }
-                        None => quote! {},
-                    }
-                } else {
-                    quote! {}
-                };
-
-                if should_derive_deref {
-                    uses_deref_derives = true;
-                    quote! {
-                        #[doc = #struct_doc]
-                        #[allow(unused_lifetimes, unused_type_parameters)]
-                        #[derive(#event_derive, Deref, DerefMut, Clone, Debug)]
-                        #propagate_attr
-                        pub struct #variant_ident #struct_generics_tokens #where_clause {
-                            #(#field_tokens)*
-                        }
-                    }
-                } else {
-                    quote! {
-                        #[doc = #struct_doc]
-                        #[allow(unused_lifetimes, unused_type_parameters)]
-                        #[derive(#event_derive, Clone, Debug)]
-                        #propagate_attr
-                        pub struct #variant_ident #struct_generics_tokens #where_clause {
-                            #(#field_tokens)*
-                        }
-                    }
-                }
-            }
-        };
-
-        struct_defs.push(struct_def);
-        if let Some(extra) = extra_impl {
-            additional_impls.push(extra);
-        }
-    }
-
-    let deref_imports = if cfg!(feature = "deref") && uses_deref_derives {
-        quote! {
-            use bevy::prelude::{Deref, DerefMut};
-        }
-    } else {
-        quote! {}
-    };
-
-    let event_import = match event_kind {
-        EventKind::EntityEvent => quote! {
-            use bevy::prelude::{Entity, EntityEvent};
-        },
-        EventKind::Message => quote! {
-            use bevy::ecs::message::Message;
-        },
-        EventKind::Event => quote! {
-            use bevy::prelude::Event;
-        },
-    };
-
-    let module_doc = match event_kind {
-        EventKind::EntityEvent => "Generated module containing entity event types for each enum variant.",
-        EventKind::Message => "Generated module containing message types for each enum variant.",
-        EventKind::Event => "Generated module containing event types for each enum variant.",
-    };
-
-    let expanded = quote! {
-        #[doc = #module_doc]
-        pub mod #module_name {
-            #[allow(unused_imports)]
-            use super::*;
-            #event_import
-            #deref_imports
-
-            #(#struct_defs)*
-            #(#additional_impls)*
-        }
-    };
-
-    TokenStream::from(expanded)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_snake_case_conversion() {
-        assert_eq!(to_snake_case("LifeFSM"), "life_fsm");
-        assert_eq!(to_snake_case("PlayerState"), "player_state");
-        assert_eq!(to_snake_case("HTTPServer"), "http_server");
-        assert_eq!(to_snake_case("FSM"), "fsm");
-        assert_eq!(to_snake_case("MyHTTPSConnection"), "my_https_connection");
-    }
+pub trait EnumFamily: Sized {
+    /// Variant names in declaration order.
+    const VARIANT_NAMES: &'static [&'static str];
+    /// Number of variants.
+    const VARIANT_COUNT: usize;
+    /// Full path of the generated module, e.g. `"my_crate::events::game_event"`.
+    const MODULE_PATH: &'static str;
+    /// Which Bevy trait the generated structs implement, unless a variant overrides it with
+    /// `#[enum_event(kind = ...)]`.
+    const EVENT_KIND: EventKind;
+
+    /// Tuple of the generated structs in declaration order, without skipped variants, e.g.
+    /// `(game_event::Victory, game_event::GameOver)`.
+    type Variants;
 }
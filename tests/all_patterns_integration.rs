@@ -318,6 +318,7 @@ fn test_enum_entity_event_propagation() {
 
 // EnumEvent for UI notifications (global)
 #[derive(EnumEvent, Clone, Debug)]
+#[allow(dead_code)]
 enum UiNotification {
     ShowToast(String),
     HideAllToasts,
@@ -325,6 +326,7 @@ enum UiNotification {
 
 // EnumMessage for command queue (buffered)
 #[derive(EnumMessage, Clone, Debug)]
+#[allow(dead_code)]
 enum GameCommand {
     SpawnEnemy { kind: String },
     DespawnAll,
@@ -332,6 +334,7 @@ enum GameCommand {
 
 // EnumEntityEvent for entity interactions (targeted)
 #[derive(EnumEntityEvent, Clone, Copy)]
+#[allow(dead_code)]
 enum InteractionEvent {
     Clicked { entity: Entity },
     Hovered { entity: Entity },
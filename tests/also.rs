@@ -0,0 +1,155 @@
+//! Tests for `#[enum_event(also = ...)]`, which makes the structs both events and messages.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_enum_event::{EnumEntityEvent, EnumEvent, EnumMessage};
+use common::Log;
+
+#[derive(EnumEvent, Clone, Debug, PartialEq)]
+#[enum_event(also = message)]
+#[allow(dead_code)]
+enum ScoreEvent {
+    Scored { team: u32, points: u32 },
+    Reset,
+}
+
+#[test]
+fn test_structs_are_events_and_messages() {
+    let mut app = App::new();
+    app.add_plugins(score_event::Plugin);
+    app.init_resource::<Log>();
+    app.add_observer(|event: On<score_event::Scored>, mut log: ResMut<Log>| {
+        log.0.push(format!("observed {}", event.points));
+    });
+    app.add_systems(
+        Update,
+        |mut reader: MessageReader<score_event::Scored>, mut log: ResMut<Log>| {
+            for message in reader.read() {
+                log.0.push(format!("read {}", message.points));
+            }
+        },
+    );
+
+    let scored = score_event::Scored { team: 1, points: 3 };
+    app.world_mut().trigger(scored.clone());
+    app.world_mut().write_message(scored);
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<Log>().0,
+        vec!["observed 3", "read 3"]
+    );
+}
+
+#[test]
+fn test_family_items_cover_both_kinds() {
+    let mut app = App::new();
+    app.add_plugins(score_event::Plugin);
+    app.init_resource::<Log>();
+    app.add_observer(|event: On<score_event::Any>, mut log: ResMut<Log>| {
+        log.0
+            .push(format!("any {:?}", ScoreEvent::from(event.event().clone())));
+    });
+    app.add_systems(
+        Update,
+        (
+            |mut writer: score_event::Writer| {
+                writer.write(ScoreEvent::Reset);
+            },
+            |mut reader: score_event::Reader, mut log: ResMut<Log>| {
+                for event in reader.read_in_write_order() {
+                    log.0.push(format!("read {event:?}"));
+                }
+            },
+        )
+            .chain(),
+    );
+
+    // Without other kinds, both dispatch styles stay infallible
+    ScoreEvent::Reset.trigger_world(app.world_mut());
+    ScoreEvent::Scored { team: 2, points: 1 }.write_world(app.world_mut());
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<Log>().0,
+        vec![
+            "any Reset",
+            "read Reset",
+            "read Scored { team: 2, points: 1 }"
+        ]
+    );
+}
+
+// `also` on a variant, and entity events that can be buffered too
+#[derive(EnumEntityEvent, Clone, Copy, Debug)]
+#[allow(dead_code)]
+enum UnitEvent {
+    #[enum_event(also = message)]
+    Died {
+        entity: Entity,
+    },
+    Healed {
+        entity: Entity,
+        amount: u32,
+    },
+}
+
+#[test]
+fn test_entity_event_also_message() {
+    let mut app = App::new();
+    app.add_plugins(unit_event::Plugin);
+    app.init_resource::<Log>();
+    let unit = app.world_mut().spawn_empty().id();
+    app.world_mut().entity_mut(unit).observe(
+        |_event: On<unit_event::Died>, mut log: ResMut<Log>| {
+            log.0.push("died".to_string());
+        },
+    );
+
+    UnitEvent::Died { entity: unit }.trigger_world(app.world_mut());
+    UnitEvent::Died { entity: unit }
+        .write_world(app.world_mut())
+        .unwrap();
+    assert!(UnitEvent::Healed {
+        entity: unit,
+        amount: 1
+    }
+    .write_world(app.world_mut())
+    .is_err());
+
+    assert_eq!(app.world().resource::<Log>().0, vec!["died"]);
+    assert_eq!(
+        app.world().resource::<Messages<unit_event::Died>>().len(),
+        1
+    );
+}
+
+#[derive(EnumMessage, Clone, Debug)]
+#[enum_event(also = event, flat, suffix = "Command")]
+#[allow(dead_code)]
+enum Network {
+    Connect(String),
+    #[enum_event(kind = event)]
+    Ping,
+}
+
+#[test]
+fn test_message_also_event() {
+    let mut app = App::new();
+    app.add_plugins(NetworkPlugin);
+    app.init_resource::<Log>();
+    app.add_observer(|event: On<ConnectCommand>, mut log: ResMut<Log>| {
+        log.0.push(format!("connect {}", event.0));
+    });
+
+    Network::Connect("a".to_string()).trigger_world(app.world_mut());
+    Network::Connect("b".to_string())
+        .write_world(app.world_mut())
+        .unwrap();
+    // `Ping` is only an event
+    assert!(Network::Ping.write_world(app.world_mut()).is_err());
+
+    assert_eq!(app.world().resource::<Log>().0, vec!["connect a"]);
+    assert_eq!(app.world().resource::<Messages<ConnectCommand>>().len(), 1);
+}
@@ -0,0 +1,190 @@
+//! Tests for the family-wide `Any` event that lets one observer handle every variant.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_enum_event::{EnumEntityEvent, EnumEvent};
+use common::Log;
+
+// ============================================================================
+// EnumEvent
+// ============================================================================
+
+#[derive(EnumEvent, Clone, Debug, PartialEq)]
+#[allow(dead_code)]
+enum GameEvent {
+    Victory(String),
+    ScoreChanged { team: u32, score: i32 },
+    GameOver,
+}
+
+#[test]
+fn test_any_observer_sees_every_variant() {
+    let mut app = App::new();
+    app.add_plugins(game_event::Plugin);
+    app.init_resource::<Log>();
+    app.add_observer(|event: On<game_event::Any>, mut log: ResMut<Log>| {
+        let value = GameEvent::from(event.event().clone());
+        log.0.push(format!("{value:?}"));
+    });
+
+    app.world_mut()
+        .trigger(game_event::ScoreChanged { team: 1, score: 2 });
+    GameEvent::Victory("red".to_string()).trigger_world(app.world_mut());
+    app.world_mut().trigger(game_event::GameOver);
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<Log>().0,
+        vec![
+            "ScoreChanged { team: 1, score: 2 }",
+            "Victory(\"red\")",
+            "GameOver"
+        ]
+    );
+}
+
+#[test]
+fn test_any_matches_variant_structs() {
+    let mut app = App::new();
+    app.add_plugins(game_event::Plugin);
+    app.init_resource::<Log>();
+    app.add_observer(
+        |event: On<game_event::Any>, mut log: ResMut<Log>| match event.event() {
+            game_event::Any::ScoreChanged(score) => {
+                log.0.push(format!("score_{}", score.score));
+            }
+            game_event::Any::Victory(_) | game_event::Any::GameOver(_) => {}
+        },
+    );
+
+    app.world_mut()
+        .trigger(game_event::ScoreChanged { team: 1, score: 9 });
+    app.world_mut().trigger(game_event::GameOver);
+    app.update();
+
+    assert_eq!(app.world().resource::<Log>().0, vec!["score_9"]);
+}
+
+#[test]
+fn test_any_round_trips_source_enum() {
+    let any = game_event::Any::from(GameEvent::ScoreChanged { team: 3, score: 4 });
+    assert!(matches!(any, game_event::Any::ScoreChanged(_)));
+    assert_eq!(
+        GameEvent::from(any),
+        GameEvent::ScoreChanged { team: 3, score: 4 }
+    );
+}
+
+#[test]
+fn test_any_generic_enum() {
+    #[derive(EnumEvent, Clone, Debug)]
+    #[allow(dead_code)]
+    enum Sample<T: Clone + std::fmt::Debug + Send + Sync + 'static> {
+        Value(T),
+        Reset,
+    }
+
+    let mut app = App::new();
+    app.add_plugins(sample::Plugin::<u32>::default());
+    app.init_resource::<Log>();
+    app.add_observer(
+        |event: On<sample::Any<u32>>, mut log: ResMut<Log>| match event.event() {
+            sample::Any::Value(value) => log.0.push(format!("value_{}", value.0)),
+            sample::Any::Reset(_) => log.0.push("reset".to_string()),
+        },
+    );
+
+    Sample::Value(5_u32).trigger_world(app.world_mut());
+    Sample::<u32>::Reset.trigger_world(app.world_mut());
+    app.update();
+
+    assert_eq!(app.world().resource::<Log>().0, vec!["value_5", "reset"]);
+}
+
+// ============================================================================
+// EnumEntityEvent
+// ============================================================================
+
+#[derive(EnumEntityEvent, Clone, Copy, Debug)]
+#[allow(dead_code)]
+enum PlayerEvent {
+    Damaged { entity: Entity, amount: u32 },
+    Healed { entity: Entity, amount: u32 },
+}
+
+#[test]
+fn test_any_entity_observer() {
+    let mut app = App::new();
+    app.add_plugins(player_event::Plugin);
+    app.init_resource::<Log>();
+
+    let player = app.world_mut().spawn_empty().id();
+    let other = app.world_mut().spawn_empty().id();
+    app.world_mut().entity_mut(player).observe(
+        |event: On<player_event::Any>, mut log: ResMut<Log>| {
+            let amount = match PlayerEvent::from(event.event().clone()) {
+                PlayerEvent::Damaged { amount, .. } => format!("damaged_{amount}"),
+                PlayerEvent::Healed { amount, .. } => format!("healed_{amount}"),
+            };
+            log.0.push(amount);
+        },
+    );
+    app.add_observer(|event: On<player_event::Any>, mut log: ResMut<Log>| {
+        log.0
+            .push(format!("global_{}", event.event_target().index()));
+    });
+
+    PlayerEvent::Damaged {
+        entity: player,
+        amount: 3,
+    }
+    .trigger_world(app.world_mut());
+    PlayerEvent::Healed {
+        entity: other,
+        amount: 1,
+    }
+    .trigger_world(app.world_mut());
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<Log>().0,
+        vec![
+            format!("global_{}", player.index()),
+            "damaged_3".to_string(),
+            format!("global_{}", other.index()),
+        ]
+    );
+}
+
+#[derive(EnumEntityEvent, Clone, Copy, Debug)]
+#[enum_event(auto_propagate, propagate)]
+#[allow(dead_code)]
+enum UiEvent {
+    Click { entity: Entity },
+}
+
+#[test]
+fn test_any_follows_propagation() {
+    let mut app = App::new();
+    app.add_plugins(ui_event::Plugin);
+    app.init_resource::<Log>();
+
+    let parent = app.world_mut().spawn_empty().id();
+    let child = app.world_mut().spawn(ChildOf(parent)).id();
+    app.world_mut().entity_mut(parent).observe(
+        |_event: On<ui_event::Any>, mut log: ResMut<Log>| {
+            log.0.push("parent".to_string());
+        },
+    );
+    app.world_mut()
+        .entity_mut(child)
+        .observe(|_event: On<ui_event::Any>, mut log: ResMut<Log>| {
+            log.0.push("child".to_string());
+        });
+
+    UiEvent::Click { entity: child }.trigger_world(app.world_mut());
+    app.update();
+
+    assert_eq!(app.world().resource::<Log>().0, vec!["child", "parent"]);
+}
@@ -0,0 +1,125 @@
+//! Tests for `#[cfg]` on variants and fields reaching all generated code.
+//!
+//! `cfg(test)` is on for this file, so `not(test)` stands in for a disabled feature.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_enum_event::{EnumEvent, EnumFamily, EnumMessage, VariantOf};
+use common::Log;
+use std::marker::PhantomData;
+
+// Only exists when the "feature" is on
+#[cfg(not(test))]
+#[derive(Clone, Debug)]
+pub struct DebugOverlay;
+
+#[derive(EnumEvent, Clone, Debug)]
+#[allow(dead_code)]
+enum ToolEvent {
+    Opened(u32),
+    #[cfg(not(test))]
+    OverlayToggled(DebugOverlay),
+    #[cfg(test)]
+    Reloaded,
+    Closed {
+        id: u32,
+        #[cfg(not(test))]
+        overlay: DebugOverlay,
+    },
+}
+
+#[test]
+fn test_cfg_variants_in_event_family() {
+    assert_eq!(ToolEvent::VARIANT_NAMES, &["Opened", "Reloaded", "Closed"]);
+    assert_eq!(ToolEvent::VARIANT_COUNT, 3);
+    assert_eq!(<tool_event::Closed as VariantOf<ToolEvent>>::INDEX, 2);
+    let _: PhantomData<(tool_event::Opened, tool_event::Reloaded, tool_event::Closed)> =
+        PhantomData::<<ToolEvent as EnumFamily>::Variants>;
+
+    let names: Vec<_> = tool_event::Kind::ALL
+        .iter()
+        .map(|kind| kind.name())
+        .collect();
+    assert_eq!(names, vec!["Opened", "Reloaded", "Closed"]);
+
+    let mut app = App::new();
+    app.add_plugins(tool_event::Plugin);
+    app.init_resource::<Log>();
+    app.add_observer(|event: On<tool_event::Any>, mut log: ResMut<Log>| {
+        log.0.push(
+            ToolEvent::from(event.event().clone())
+                .kind()
+                .name()
+                .to_string(),
+        );
+    });
+
+    ToolEvent::Closed { id: 1 }.trigger_world(app.world_mut());
+    ToolEvent::Reloaded.trigger_world(app.world_mut());
+    app.update();
+
+    assert_eq!(app.world().resource::<Log>().0, vec!["Closed", "Reloaded"]);
+    assert!(matches!(
+        ToolEvent::from(tool_event::Closed { id: 4 }),
+        ToolEvent::Closed { id: 4 }
+    ));
+}
+
+#[derive(EnumMessage, Clone, Debug)]
+#[allow(dead_code)]
+enum ToolMessage {
+    Ping,
+    #[cfg(not(test))]
+    Overlay(DebugOverlay),
+    Pong {
+        #[cfg(test)]
+        seq: u32,
+    },
+}
+
+#[test]
+fn test_cfg_variants_in_message_family() {
+    let mut app = App::new();
+    app.add_plugins(tool_message::Plugin);
+    app.init_resource::<Log>();
+    app.add_systems(
+        Update,
+        (
+            |mut writer: tool_message::Writer| {
+                writer.write(ToolMessage::Pong { seq: 2 });
+                writer.write(ToolMessage::Ping);
+            },
+            |mut reader: tool_message::Reader, mut log: ResMut<Log>| {
+                assert_eq!(reader.len(), 2);
+                for message in reader.read_in_write_order() {
+                    log.0.push(format!("{message:?}"));
+                }
+                assert!(reader.is_empty());
+            },
+        )
+            .chain(),
+    );
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<Log>().0,
+        vec!["Pong { seq: 2 }", "Ping"]
+    );
+    assert_eq!(tool_message::Kind::ALL.len(), 2);
+}
+
+#[derive(EnumMessage, Clone, Debug)]
+#[allow(dead_code)]
+enum Probe<T: Clone + Send + Sync + 'static> {
+    Sample(T, #[cfg(not(test))] DebugOverlay),
+    #[cfg(not(test))]
+    Overlay(DebugOverlay),
+    Done,
+}
+
+#[test]
+fn test_cfg_in_generic_enum_and_tuple_fields() {
+    assert_eq!(Probe::<u8>::VARIANT_NAMES, &["Sample", "Done"]);
+    assert!(matches!(Probe::from(probe::Sample(3_u8)), Probe::Sample(3)));
+}
@@ -0,0 +1,17 @@
+//! Fixtures shared by the integration tests.
+#![allow(dead_code)]
+
+use bevy::prelude::*;
+use bevy_enum_event::EnumEvent;
+
+/// What observers and systems saw, in order.
+#[derive(Resource, Default)]
+pub struct Log(pub Vec<String>);
+
+/// A plain event family with a tuple, a named and a unit variant.
+#[derive(EnumEvent, Clone, Debug, PartialEq)]
+pub enum GameEvent {
+    Victory(String),
+    ScoreChanged { team: u32, score: i32 },
+    GameOver,
+}
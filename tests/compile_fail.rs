@@ -0,0 +1,9 @@
+//! Compile-fail tests: every misuse of the derives is reported as a spanned compile error.
+
+#[test]
+fn test_compile_errors() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+    #[cfg(not(feature = "deref"))]
+    cases.compile_fail("tests/ui/no_deref_feature/*.rs");
+}
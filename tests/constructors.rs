@@ -0,0 +1,100 @@
+//! Tests for the generated `new`, `into_parts`/`from_parts` and builders.
+
+use bevy::prelude::*;
+use bevy_enum_event::{EnumEntityEvent, EnumEvent, EnumMessage};
+
+const TEAM_SIZE: u8 = 5;
+
+#[derive(EnumEvent, Clone, Debug, PartialEq)]
+#[allow(dead_code)]
+enum GameEvent {
+    ScoreChanged {
+        team: u8,
+        score: u32,
+        #[enum_event(default)]
+        bonus: u32,
+        #[enum_event(default = TEAM_SIZE)]
+        players: u8,
+    },
+    Chat(u32, String),
+    Paused,
+}
+
+// `new` works in constant contexts
+const KICKOFF: game_event::ScoreChanged = game_event::ScoreChanged::new(0, 0, 0, TEAM_SIZE);
+
+#[test]
+fn test_new() {
+    assert_eq!(KICKOFF.players, 5);
+    assert_eq!(game_event::Chat::new(1, "hi".to_string()).1, "hi");
+    assert_eq!(
+        GameEvent::from(game_event::Paused::new()),
+        GameEvent::Paused
+    );
+}
+
+#[test]
+fn test_parts() {
+    let chat = game_event::Chat::new(2, "gg".to_string());
+    let (player, text) = chat.into_parts();
+    assert_eq!((player, text.as_str()), (2, "gg"));
+    assert_eq!(
+        game_event::ScoreChanged::from_parts((1, 3, 0, 4)).into_parts(),
+        (1, 3, 0, 4)
+    );
+    let () = game_event::Paused::from_parts(()).into_parts();
+}
+
+#[test]
+fn test_builder() {
+    let built = game_event::ScoreChanged::builder().score(3).team(1).build();
+    assert_eq!(built.into_parts(), (1, 3, 0, TEAM_SIZE));
+
+    let built = game_event::ScoreChanged::builder()
+        .bonus(2)
+        .team(1)
+        .players(3)
+        .score(4)
+        .team(2)
+        .build();
+    assert_eq!(built.into_parts(), (2, 4, 2, 3));
+}
+
+#[derive(EnumMessage, Clone, Debug)]
+#[allow(dead_code)]
+enum Inventory<T: Clone + Send + Sync + 'static> {
+    Added {
+        item: T,
+        #[enum_event(default = 1)]
+        count: u32,
+    },
+    Cleared,
+}
+
+#[test]
+fn test_generic_constructors() {
+    let added = inventory::Added::builder().item("sword").build();
+    assert_eq!((added.item, added.count), ("sword", 1));
+    assert_eq!(inventory::Added::new('x', 2).into_parts(), ('x', 2));
+    let _cleared: inventory::Cleared<u8> = inventory::Cleared::from_parts(());
+}
+
+#[derive(EnumEntityEvent, Clone, Copy, Debug)]
+#[enum_event(flat)]
+#[allow(dead_code)]
+enum UnitEvent {
+    Damaged {
+        entity: Entity,
+        #[enum_event(default)]
+        amount: u32,
+    },
+}
+
+#[test]
+fn test_flat_builder() {
+    let mut world = World::new();
+    let unit = world.spawn_empty().id();
+    let builder: DamagedBuilder<()> = Damaged::builder();
+    let damaged = builder.entity(unit).build();
+    assert_eq!((damaged.entity, damaged.amount), (unit, 0));
+}
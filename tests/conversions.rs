@@ -0,0 +1,118 @@
+//! Tests for the generated `From`/`TryFrom`/`PartialEq` impls between the source enum
+//! and its variant structs.
+
+mod common;
+
+use bevy::prelude::Entity;
+use bevy_enum_event::{EnumEntityEvent, EnumEvent, EnumMessage};
+use common::{game_event, GameEvent};
+
+#[test]
+fn test_struct_into_enum() {
+    let victory: GameEvent = game_event::Victory("red".to_string()).into();
+    assert_eq!(victory, GameEvent::Victory("red".to_string()));
+
+    let score = GameEvent::from(game_event::ScoreChanged { team: 1, score: 3 });
+    assert_eq!(score, GameEvent::ScoreChanged { team: 1, score: 3 });
+
+    let game_over: GameEvent = game_event::GameOver.into();
+    assert_eq!(game_over, GameEvent::GameOver);
+}
+
+#[test]
+fn test_enum_try_into_struct() {
+    let score = game_event::ScoreChanged::try_from(GameEvent::ScoreChanged { team: 2, score: 5 })
+        .expect("matching variant should convert");
+    assert_eq!(score.team, 2);
+    assert_eq!(score.score, 5);
+
+    let victory: game_event::Victory = GameEvent::Victory("blue".to_string())
+        .try_into()
+        .expect("matching variant should convert");
+    assert_eq!(victory.0, "blue");
+
+    assert!(game_event::GameOver::try_from(GameEvent::GameOver).is_ok());
+}
+
+#[test]
+fn test_try_from_returns_original_on_mismatch() {
+    let err = game_event::GameOver::try_from(GameEvent::Victory("green".to_string()))
+        .expect_err("different variant should not convert");
+    assert_eq!(err, GameEvent::Victory("green".to_string()));
+}
+
+#[test]
+fn test_partial_eq_between_enum_and_struct() {
+    let value = GameEvent::ScoreChanged { team: 1, score: 3 };
+    assert!(value == game_event::ScoreChanged { team: 1, score: 3 });
+    assert!(game_event::ScoreChanged { team: 1, score: 3 } == value);
+    assert!(value != game_event::ScoreChanged { team: 1, score: 4 });
+    assert!(value != game_event::GameOver);
+    assert!(game_event::GameOver == GameEvent::GameOver);
+    assert!(game_event::Victory("red".to_string()) != GameEvent::GameOver);
+}
+
+// Payloads without PartialEq still get From/TryFrom; only the comparison impl is unavailable.
+#[derive(Clone, Debug)]
+pub struct Opaque;
+
+#[derive(EnumMessage, Clone)]
+#[allow(dead_code)]
+enum OpaqueMessage {
+    Payload(Opaque),
+    Empty,
+}
+
+#[test]
+fn test_non_comparable_payload_converts() {
+    let message: OpaqueMessage = opaque_message::Payload(Opaque).into();
+    assert!(opaque_message::Payload::try_from(message).is_ok());
+    assert!(opaque_message::Empty == OpaqueMessage::Empty);
+}
+
+#[test]
+fn test_generic_enum_conversions() {
+    #[derive(EnumEvent, Clone, Debug, PartialEq)]
+    #[allow(dead_code)]
+    enum GenericEvent<T>
+    where
+        T: Clone + std::fmt::Debug + PartialEq,
+    {
+        Owned(T),
+        Labeled { label: String },
+        Unit,
+    }
+
+    let owned: GenericEvent<u32> = generic_event::Owned(7).into();
+    assert_eq!(owned, GenericEvent::Owned(7));
+
+    // `Labeled` and `Unit` don't mention `T`, so their structs carry a `_phantom` field.
+    let labeled: GenericEvent<u32> = generic_event::Labeled::new("tag".to_string()).into();
+    assert_eq!(
+        labeled,
+        GenericEvent::Labeled {
+            label: "tag".to_string()
+        }
+    );
+    let back = generic_event::Labeled::<u32>::try_from(labeled).unwrap();
+    assert_eq!(back.label, "tag");
+
+    let unit = generic_event::Unit::<u32>::try_from(GenericEvent::Unit).unwrap();
+    assert!(unit == GenericEvent::<u32>::Unit);
+    assert!(GenericEvent::Owned(1) != generic_event::Unit::<u32>::new());
+}
+
+#[test]
+fn test_entity_event_conversions() {
+    #[derive(EnumEntityEvent, Clone, Copy, Debug, PartialEq)]
+    #[allow(dead_code)]
+    enum PlayerEvent {
+        Damaged { entity: Entity, amount: u32 },
+        Spawned { entity: Entity },
+    }
+
+    let entity = Entity::from_bits(3);
+    let damaged: PlayerEvent = player_event::Damaged { entity, amount: 5 }.into();
+    assert_eq!(damaged, PlayerEvent::Damaged { entity, amount: 5 });
+    assert!(player_event::Spawned::try_from(damaged).is_err());
+}
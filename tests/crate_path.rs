@@ -0,0 +1,145 @@
+//! Tests for `#[enum_event(crate = path)]`, which points the generated code at another Bevy path.
+
+mod common;
+
+use bevy::ecs::message::MessageRegistry;
+use bevy::prelude::*;
+use bevy_enum_event::{EnumEvent, EnumFamily};
+use common::Log;
+
+// Bevy's own derives look up `bevy_ecs` where they are used; the generated code provides the
+// alias itself, also for two flat enums in one module
+mod headless {
+    use bevy_enum_event::{EnumEvent, EnumMessage};
+
+    #[derive(EnumMessage, Clone, Debug, PartialEq)]
+    #[enum_event(crate = bevy::ecs)]
+    #[allow(dead_code)]
+    pub enum ServerCommand {
+        Kick(u32),
+        Shutdown { delay: u32 },
+    }
+
+    #[derive(EnumEvent, Clone, Debug)]
+    #[enum_event(crate = bevy::ecs, flat, suffix = "Event")]
+    #[allow(dead_code)]
+    pub enum ServerEvent {
+        Started,
+        Tick(u64),
+    }
+
+    #[derive(EnumMessage, Clone, Debug)]
+    #[enum_event(crate = bevy::ecs, flat, suffix = "Message")]
+    #[allow(dead_code)]
+    pub enum ServerNotice {
+        Lagging(u32),
+    }
+}
+
+// A hand-written alias shadows the generated one
+mod aliased {
+    use bevy::ecs as bevy_ecs;
+    use bevy_enum_event::EnumEvent;
+
+    #[derive(EnumEvent, Clone, Debug)]
+    #[enum_event(crate = bevy_ecs, flat)]
+    #[allow(dead_code)]
+    pub enum Heartbeat {
+        Beat,
+    }
+}
+
+mod engine {
+    pub use bevy;
+}
+
+#[derive(EnumEvent, Clone, Debug)]
+#[enum_event(crate = engine::bevy)]
+#[allow(dead_code)]
+enum GameEvent {
+    Scored(u32),
+    GameOver,
+}
+
+#[test]
+fn test_ecs_only_messages() {
+    use headless::{server_command, ServerCommand};
+
+    // Without a plugin the variants and the documented `WriteOrder` are registered by hand
+    let mut world = World::new();
+    MessageRegistry::register_message::<server_command::Kick>(&mut world);
+    MessageRegistry::register_message::<server_command::Shutdown>(&mut world);
+    MessageRegistry::register_message::<server_command::WriteOrder>(&mut world);
+    world.init_resource::<Log>();
+
+    let mut write = IntoSystem::into_system(|mut writer: server_command::Writer| {
+        writer.write(ServerCommand::Shutdown { delay: 5 });
+        writer.write(ServerCommand::Kick(2));
+    });
+    write.initialize(&mut world);
+    write.run((), &mut world).unwrap();
+
+    let mut read =
+        IntoSystem::into_system(|mut reader: server_command::Reader, mut log: ResMut<Log>| {
+            for command in reader.read_in_write_order() {
+                log.0.push(format!("{command:?}"));
+            }
+        });
+    read.initialize(&mut world);
+    read.run((), &mut world).unwrap();
+
+    assert_eq!(
+        world.resource::<Log>().0,
+        vec!["Shutdown { delay: 5 }", "Kick(2)"]
+    );
+    assert_eq!(ServerCommand::VARIANT_COUNT, 2);
+}
+
+#[test]
+fn test_ecs_only_flat_events() {
+    use headless::{ServerEvent, ServerEventKind, TickEvent};
+
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    world.add_observer(|event: On<TickEvent>, mut log: ResMut<Log>| {
+        log.0.push(format!("tick_{}", event.0));
+    });
+
+    ServerEvent::Tick(3).trigger_world(&mut world);
+    world.flush();
+
+    assert_eq!(world.resource::<Log>().0, vec!["tick_3"]);
+    assert_eq!(TickEvent(1).kind(), ServerEventKind::Tick);
+    assert_eq!(ServerEvent::Started.kind(), ServerEventKind::Started);
+
+    MessageRegistry::register_message::<headless::LaggingMessage>(&mut world);
+    headless::ServerNotice::Lagging(40).write_world(&mut world);
+    assert_eq!(
+        world.resource::<Messages<headless::LaggingMessage>>().len(),
+        1
+    );
+    let _ = aliased::Beat;
+}
+
+#[test]
+fn test_reexported_facade() {
+    let mut app = App::new();
+    app.add_plugins(game_event::Plugin);
+    app.init_resource::<Log>();
+    app.add_observer(|event: On<game_event::Any>, mut log: ResMut<Log>| {
+        log.0.push(format!(
+            "{:?}",
+            GameEvent::from(event.event().clone()).kind()
+        ));
+    });
+
+    GameEvent::Scored(1).trigger_world(app.world_mut());
+    app.world_mut().trigger(game_event::GameOver);
+    app.update();
+
+    assert_eq!(app.world().resource::<Log>().0, vec!["Scored", "GameOver"]);
+    #[cfg(feature = "deref")]
+    assert_eq!(*game_event::Scored(4), 4);
+    #[cfg(not(feature = "deref"))]
+    assert_eq!(game_event::Scored(4).0, 4);
+}
@@ -0,0 +1,102 @@
+//! Tests for the per-enum and per-variant `deref` policy.
+
+use bevy_enum_event::{EnumEvent, EnumMessage};
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+// Inherent methods win over trait methods, but only apply when `T: Deref`
+struct Probe<T>(PhantomData<T>);
+
+trait NoDeref {
+    fn has_deref(&self) -> bool {
+        false
+    }
+}
+
+impl<T> NoDeref for Probe<T> {}
+
+impl<T: Deref> Probe<T> {
+    fn has_deref(&self) -> bool {
+        true
+    }
+}
+
+macro_rules! has_deref {
+    ($ty:ty) => {
+        Probe::<$ty>(PhantomData).has_deref()
+    };
+}
+
+#[derive(EnumEvent, Clone, Debug)]
+#[enum_event(deref = false)]
+#[allow(dead_code)]
+enum Chat {
+    Said(String),
+    Whispered {
+        text: String,
+        to: u32,
+    },
+    #[enum_event(deref)]
+    Shouted(String),
+}
+
+// With the derive off, the struct is free to deref to something else
+impl Deref for chat::Said {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.0.trim()
+    }
+}
+
+#[test]
+fn test_enum_policy_off() {
+    assert_eq!(&*chat::Said(" hi ".to_string()), "hi");
+    assert!(!has_deref!(chat::Whispered));
+    // A variant can turn it back on
+    assert_eq!(chat::Shouted("HEY".to_string()).len(), 3);
+    assert!(has_deref!(chat::Shouted));
+}
+
+#[derive(EnumMessage, Clone, Debug)]
+#[allow(dead_code)]
+enum Input {
+    Key(char),
+    #[enum_event(no_deref)]
+    Scroll(f32),
+    #[enum_event(deref = false)]
+    Click {
+        button: u8,
+        x: f32,
+    },
+}
+
+#[test]
+fn test_variant_policy_off() {
+    assert!(!has_deref!(input::Scroll));
+    assert!(!has_deref!(input::Click));
+    assert_eq!(has_deref!(input::Key), cfg!(feature = "deref"));
+}
+
+// Explicit opt-ins apply whatever the `deref` feature says
+#[derive(EnumEvent, Clone, Debug)]
+#[enum_event(deref)]
+#[allow(dead_code)]
+enum Score {
+    Total(u32),
+    Added {
+        #[enum_event(deref)]
+        points: u32,
+        team: u8,
+    },
+    Reset,
+}
+
+#[test]
+fn test_explicit_opt_in() {
+    assert_eq!(*score::Total(4), 4);
+    let mut added = score::Added { points: 2, team: 1 };
+    *added += 1;
+    assert_eq!(added.points, 3);
+    assert!(!has_deref!(score::Reset));
+}
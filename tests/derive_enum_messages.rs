@@ -244,6 +244,7 @@ enum TestNetworkMessage {
 #[derive(Resource, Default)]
 struct ReceivedMessages {
     connections: Vec<u32>,
+    #[allow(dead_code)]
     disconnections: Vec<(u32, String)>,
     data_packets: Vec<Vec<u8>>,
 }
@@ -428,3 +429,257 @@ fn test_multiple_readers_same_message() {
     assert_eq!(count1.0, 1, "Reader 1 should have read 1 message");
     assert_eq!(count2.0, 1, "Reader 2 should have read 1 message");
 }
+
+// ============================================================================
+// Generated Plugin
+// ============================================================================
+
+#[derive(EnumMessage, Clone, Debug)]
+#[allow(dead_code)]
+enum NetworkCommand {
+    Connect { address: String },
+    Disconnect,
+    SendData(Vec<u8>),
+}
+
+#[test]
+fn test_plugin_registers_every_variant() {
+    let mut app = App::new();
+    app.add_plugins(network_command::Plugin);
+
+    assert!(app
+        .world()
+        .contains_resource::<Messages<network_command::Connect>>());
+    assert!(app
+        .world()
+        .contains_resource::<Messages<network_command::Disconnect>>());
+    assert!(app
+        .world()
+        .contains_resource::<Messages<network_command::SendData>>());
+
+    // Writing any variant no longer needs a manual add_message call
+    NetworkCommand::SendData(vec![1, 2]).write_world(app.world_mut());
+    let sent = app
+        .world()
+        .resource::<Messages<network_command::SendData>>();
+    assert_eq!(sent.len(), 1);
+}
+
+#[test]
+fn test_generic_plugin_registers_instantiation() {
+    #[derive(EnumMessage, Clone, Debug)]
+    #[allow(dead_code)]
+    enum GenericCommand<T: Clone + Send + Sync + 'static> {
+        Set(T),
+        Clear,
+    }
+
+    let mut app = App::new();
+    app.add_plugins(generic_command::Plugin::<u32>::default());
+
+    assert!(app
+        .world()
+        .contains_resource::<Messages<generic_command::Set<u32>>>());
+    assert!(app
+        .world()
+        .contains_resource::<Messages<generic_command::Clear<u32>>>());
+}
+
+// ============================================================================
+// Family Reader / Writer
+// ============================================================================
+
+#[derive(EnumMessage, Clone, Debug, PartialEq)]
+#[allow(dead_code)]
+enum ChatMessage {
+    Joined(u32),
+    Said { player: u32, text: String },
+    Left(u32),
+}
+
+#[derive(Resource, Default)]
+struct ChatLog(Vec<ChatMessage>);
+
+fn chat_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(chat_message::Plugin);
+    app.init_resource::<ChatLog>();
+    app
+}
+
+#[test]
+fn test_family_reader_groups_by_variant() {
+    let mut app = chat_app();
+
+    app.add_systems(
+        Update,
+        (
+            |mut left: MessageWriter<chat_message::Left>,
+             mut joined: MessageWriter<chat_message::Joined>| {
+                left.write(chat_message::Left(1));
+                joined.write(chat_message::Joined(2));
+                joined.write(chat_message::Joined(3));
+            },
+            |mut reader: chat_message::Reader, mut log: ResMut<ChatLog>| {
+                assert_eq!(reader.len(), 3);
+                log.0.extend(reader.read());
+                assert!(reader.is_empty());
+            },
+        )
+            .chain(),
+    );
+    app.update();
+
+    // Declaration order of variants, write order within a variant
+    assert_eq!(
+        app.world().resource::<ChatLog>().0,
+        vec![
+            ChatMessage::Joined(2),
+            ChatMessage::Joined(3),
+            ChatMessage::Left(1)
+        ]
+    );
+}
+
+#[test]
+fn test_family_reader_global_write_order() {
+    let mut app = chat_app();
+
+    app.add_systems(
+        Update,
+        (
+            |mut writer: chat_message::Writer| {
+                writer.write(ChatMessage::Left(1));
+                writer.write(ChatMessage::Said {
+                    player: 2,
+                    text: "hi".to_string(),
+                });
+                writer.write(ChatMessage::Joined(3));
+                writer.write(ChatMessage::Left(4));
+            },
+            |mut reader: chat_message::Reader, mut log: ResMut<ChatLog>| {
+                log.0.extend(reader.read_in_write_order());
+            },
+        )
+            .chain(),
+    );
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<ChatLog>().0,
+        vec![
+            ChatMessage::Left(1),
+            ChatMessage::Said {
+                player: 2,
+                text: "hi".to_string()
+            },
+            ChatMessage::Joined(3),
+            ChatMessage::Left(4),
+        ]
+    );
+}
+
+#[test]
+fn test_family_reader_write_order_with_direct_writes() {
+    let mut app = chat_app();
+
+    app.add_systems(
+        Update,
+        (
+            |mut joined: MessageWriter<chat_message::Joined>| {
+                joined.write(chat_message::Joined(9));
+            },
+            |mut writer: chat_message::Writer| {
+                writer.write(ChatMessage::Joined(3));
+                writer.write(ChatMessage::Left(1));
+            },
+            |mut reader: chat_message::Reader, mut log: ResMut<ChatLog>| {
+                log.0.extend(reader.read_in_write_order());
+            },
+        )
+            .chain(),
+    );
+    app.update();
+
+    // Every message is yielded exactly once, the direct write after the ordered ones
+    assert_eq!(
+        app.world().resource::<ChatLog>().0,
+        vec![
+            ChatMessage::Joined(3),
+            ChatMessage::Left(1),
+            ChatMessage::Joined(9)
+        ]
+    );
+}
+
+#[test]
+fn test_generic_family_reader() {
+    #[derive(EnumMessage, Clone, Debug, PartialEq)]
+    #[allow(dead_code)]
+    enum Sample<T: Clone + Send + Sync + 'static> {
+        Value(T),
+        Reset,
+    }
+
+    #[derive(Resource, Default)]
+    struct SampleLog(Vec<Sample<u32>>);
+
+    let mut app = App::new();
+    app.add_plugins(sample::Plugin::<u32>::default());
+    app.init_resource::<SampleLog>();
+    app.add_systems(
+        Update,
+        (
+            |mut writer: sample::Writer<u32>| {
+                writer.write(Sample::Reset);
+                writer.write(Sample::Value(5));
+            },
+            |mut reader: sample::Reader<u32>, mut log: ResMut<SampleLog>| {
+                log.0.extend(reader.read_in_write_order());
+            },
+        )
+            .chain(),
+    );
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<SampleLog>().0,
+        vec![Sample::Reset, Sample::Value(5)]
+    );
+}
+
+#[test]
+fn test_generic_family_with_default() {
+    #[derive(EnumMessage, Clone, Debug, PartialEq)]
+    #[allow(dead_code)]
+    enum Net<T: Clone + Send + Sync + std::fmt::Debug + 'static = u32> {
+        Data(T),
+        Empty,
+    }
+
+    #[derive(Resource, Default)]
+    struct NetLog(Vec<Net>);
+
+    let mut app = App::new();
+    app.add_plugins(net::Plugin::<u32>::default());
+    app.init_resource::<NetLog>();
+    app.add_systems(
+        Update,
+        (
+            |mut writer: net::Writer| {
+                writer.write(Net::Data(7));
+                writer.write(Net::Empty);
+            },
+            |mut reader: net::Reader, mut log: ResMut<NetLog>| {
+                log.0.extend(reader.read_in_write_order());
+            },
+        )
+            .chain(),
+    );
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<NetLog>().0,
+        vec![Net::Data(7), Net::Empty]
+    );
+}
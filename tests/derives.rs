@@ -0,0 +1,160 @@
+//! Tests for controlling the derives of generated structs.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_enum_event::{EnumEvent, EnumMessage};
+use common::Log;
+use std::collections::HashSet;
+use std::sync::mpsc::Receiver;
+use std::sync::Mutex;
+
+#[derive(EnumEvent, Clone, Debug)]
+#[enum_event(derive(PartialEq, Eq, Hash))]
+#[allow(dead_code)]
+enum InputEvent {
+    Pressed(u32),
+    #[enum_event(derive(PartialOrd, Ord))]
+    Released {
+        code: u32,
+    },
+    Cleared,
+}
+
+#[test]
+fn test_extra_derives() {
+    let pressed: HashSet<_> = [
+        input_event::Pressed(1),
+        input_event::Pressed(1),
+        input_event::Pressed(2),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(pressed.len(), 2);
+
+    assert!(input_event::Released { code: 1 } < input_event::Released { code: 2 });
+    assert_eq!(input_event::Cleared, input_event::Cleared);
+}
+
+// The payload is neither Clone nor Debug
+#[derive(EnumEvent)]
+#[enum_event(no_clone, no_debug)]
+#[allow(dead_code)]
+enum CallbackEvent {
+    Run(Box<dyn Fn() -> u32 + Send + Sync>),
+    Stop,
+}
+
+#[test]
+fn test_no_clone_no_debug() {
+    let mut app = App::new();
+    app.add_plugins(callback_event::Plugin);
+    app.init_resource::<Log>();
+    app.add_observer(|event: On<callback_event::Run>, mut log: ResMut<Log>| {
+        log.0.push(format!("run_{}", (event.0)()));
+    });
+    app.add_observer(|_event: On<callback_event::Any>, mut log: ResMut<Log>| {
+        log.0.push("any".to_string());
+    });
+
+    CallbackEvent::Run(Box::new(|| 7)).trigger_world(app.world_mut());
+    app.update();
+
+    // Variants without `Clone` are not forwarded to `Any`
+    assert_eq!(app.world().resource::<Log>().0, vec!["run_7"]);
+}
+
+#[derive(EnumMessage)]
+#[allow(dead_code)]
+enum WorkerMessage {
+    #[enum_event(no_clone)]
+    Attach(Mutex<Receiver<u32>>),
+    #[enum_event(no_debug)]
+    Detach,
+}
+
+#[test]
+fn test_variant_opt_outs() {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let mut app = App::new();
+    app.add_plugins(worker_message::Plugin);
+    app.world_mut()
+        .write_message(worker_message::Attach(Mutex::new(receiver)));
+    sender.send(5).unwrap();
+
+    let attach = app
+        .world_mut()
+        .resource_mut::<Messages<worker_message::Attach>>()
+        .drain()
+        .next()
+        .unwrap();
+    assert_eq!(attach.0.lock().unwrap().recv().unwrap(), 5);
+    assert!(format!("{attach:?}").starts_with("Attach(Mutex"));
+    // `no_debug` alone keeps `Clone` and `Copy`
+    let detach = worker_message::Detach;
+    let _ = (detach, detach);
+}
+
+// Derive macros don't see their own `#[derive]` attribute, so the forwarded list sits in a second
+// one below it (kept apart so rustfmt doesn't merge them)
+#[derive(EnumMessage)]
+#[enum_event(forward_derives)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[allow(dead_code)]
+enum Direction {
+    Up(i8),
+    Down { step: i8 },
+}
+
+#[test]
+fn test_forward_derives() {
+    let up = direction::Up(1);
+    let copied = up;
+    assert_eq!(up, copied);
+    assert!(direction::Down { step: 1 } < direction::Down { step: 2 });
+    let set: HashSet<_> = [up, copied].into_iter().collect();
+    assert_eq!(set.len(), 1);
+}
+
+#[derive(EnumMessage, Clone, Debug)]
+#[allow(dead_code)]
+enum Inventory {
+    #[enum_event(derive(Reflect))]
+    Added {
+        item: u32,
+    },
+    Removed(u32),
+}
+
+#[derive(EnumEvent)]
+#[enum_event(forward_derives)]
+#[derive(Clone, Debug, Reflect)]
+#[allow(dead_code)]
+enum Weather {
+    Rain(f32),
+    Clear,
+}
+
+#[derive(EnumMessage, Clone, Debug)]
+#[enum_event(derive(Reflect))]
+#[allow(dead_code)]
+enum Reading<T: Reflect + TypePath + Clone> {
+    Value(T),
+    Reset,
+}
+
+#[test]
+fn test_plugin_registers_reflect_structs() {
+    let mut app = App::new();
+    app.add_plugins(inventory::Plugin);
+    app.add_plugins(weather::Plugin);
+    app.add_plugins(reading::Plugin::<u32>::default());
+
+    let registry = app.world().resource::<AppTypeRegistry>().read();
+    assert!(registry.contains(std::any::TypeId::of::<inventory::Added>()));
+    assert!(!registry.contains(std::any::TypeId::of::<inventory::Removed>()));
+    assert!(registry.contains(std::any::TypeId::of::<weather::Rain>()));
+    assert!(registry.contains(std::any::TypeId::of::<weather::Clear>()));
+    assert!(registry.contains(std::any::TypeId::of::<reading::Value<u32>>()));
+    assert!(registry.contains(std::any::TypeId::of::<reading::Reset<u32>>()));
+}
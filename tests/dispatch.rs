@@ -0,0 +1,210 @@
+//! Tests for triggering/writing a source enum value directly, without matching it back
+//! into the generated variant structs by hand.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_enum_event::{EnumEntityEvent, EnumEvent, EnumMessage};
+use common::Log;
+
+// ============================================================================
+// EnumEvent
+// ============================================================================
+
+#[derive(EnumEvent, Clone, Debug)]
+#[allow(dead_code)]
+enum GameEvent {
+    Victory(String),
+    ScoreChanged { team: u32, score: i32 },
+    GameOver,
+}
+
+fn game_event_app() -> App {
+    let mut app = App::new();
+    app.init_resource::<Log>();
+    app.add_observer(|event: On<game_event::Victory>, mut log: ResMut<Log>| {
+        log.0.push(format!("victory_{}", event.0));
+    });
+    app.add_observer(
+        |event: On<game_event::ScoreChanged>, mut log: ResMut<Log>| {
+            log.0.push(format!("score_{}_{}", event.team, event.score));
+        },
+    );
+    app.add_observer(|_event: On<game_event::GameOver>, mut log: ResMut<Log>| {
+        log.0.push("game_over".to_string());
+    });
+    app
+}
+
+#[test]
+fn test_trigger_through_commands() {
+    let mut app = game_event_app();
+
+    // Values computed at runtime, e.g. from a lookup table
+    let events = [
+        GameEvent::ScoreChanged { team: 1, score: 3 },
+        GameEvent::Victory("red".to_string()),
+        GameEvent::GameOver,
+    ];
+    app.add_systems(Update, move |mut commands: Commands| {
+        for event in events.clone() {
+            event.trigger(&mut commands);
+        }
+    });
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<Log>().0,
+        vec!["score_1_3", "victory_red", "game_over"]
+    );
+}
+
+#[test]
+fn test_trigger_world() {
+    let mut app = game_event_app();
+
+    GameEvent::Victory("blue".to_string()).trigger_world(app.world_mut());
+    GameEvent::GameOver.trigger_world(app.world_mut());
+
+    assert_eq!(
+        app.world().resource::<Log>().0,
+        vec!["victory_blue", "game_over"]
+    );
+}
+
+#[test]
+fn test_queue_variant_struct_as_command() {
+    let mut app = game_event_app();
+
+    app.add_systems(Update, |mut commands: Commands| {
+        commands.queue(game_event::ScoreChanged { team: 2, score: 7 });
+    });
+    app.update();
+
+    assert_eq!(app.world().resource::<Log>().0, vec!["score_2_7"]);
+}
+
+#[test]
+fn test_trigger_generic_enum() {
+    #[derive(EnumEvent, Clone, Debug)]
+    #[allow(dead_code)]
+    enum GenericEvent<T: Clone + Send + Sync + 'static> {
+        Value(T),
+        Reset,
+    }
+
+    let mut app = App::new();
+    app.init_resource::<Log>();
+    app.add_observer(
+        |event: On<generic_event::Value<u32>>, mut log: ResMut<Log>| {
+            log.0.push(format!("value_{}", event.0));
+        },
+    );
+    app.add_observer(
+        |_event: On<generic_event::Reset<u32>>, mut log: ResMut<Log>| {
+            log.0.push("reset".to_string());
+        },
+    );
+
+    GenericEvent::Value(5_u32).trigger_world(app.world_mut());
+    GenericEvent::<u32>::Reset.trigger_world(app.world_mut());
+
+    assert_eq!(app.world().resource::<Log>().0, vec!["value_5", "reset"]);
+}
+
+// ============================================================================
+// EnumEntityEvent
+// ============================================================================
+
+#[derive(EnumEntityEvent, Clone, Copy, Debug)]
+#[allow(dead_code)]
+enum PlayerEvent {
+    Damaged { entity: Entity, amount: u32 },
+    Healed { entity: Entity, amount: u32 },
+}
+
+#[test]
+fn test_trigger_entity_event() {
+    let mut app = App::new();
+    app.init_resource::<Log>();
+
+    let player = app.world_mut().spawn_empty().id();
+    let other = app.world_mut().spawn_empty().id();
+    app.world_mut().entity_mut(player).observe(
+        |event: On<player_event::Damaged>, mut log: ResMut<Log>| {
+            log.0.push(format!("damaged_{}", event.amount));
+        },
+    );
+    app.world_mut().entity_mut(player).observe(
+        |event: On<player_event::Healed>, mut log: ResMut<Log>| {
+            log.0.push(format!("healed_{}", event.amount));
+        },
+    );
+
+    app.add_systems(Update, move |mut commands: Commands| {
+        PlayerEvent::Damaged {
+            entity: player,
+            amount: 4,
+        }
+        .trigger(&mut commands);
+        // Targets a different entity, so the player's observers must not run
+        PlayerEvent::Damaged {
+            entity: other,
+            amount: 100,
+        }
+        .trigger(&mut commands);
+    });
+    app.update();
+
+    PlayerEvent::Healed {
+        entity: player,
+        amount: 2,
+    }
+    .trigger_world(app.world_mut());
+
+    assert_eq!(
+        app.world().resource::<Log>().0,
+        vec!["damaged_4", "healed_2"]
+    );
+}
+
+// ============================================================================
+// EnumMessage
+// ============================================================================
+
+#[derive(EnumMessage, Clone, Debug)]
+#[allow(dead_code)]
+enum NetworkCommand {
+    Connect { address: String },
+    Disconnect,
+}
+
+#[test]
+fn test_write_messages() {
+    let mut app = App::new();
+    app.add_message::<network_command::Connect>();
+    app.add_message::<network_command::Disconnect>();
+
+    app.add_systems(Update, |mut commands: Commands| {
+        NetworkCommand::Connect {
+            address: "127.0.0.1".to_string(),
+        }
+        .write(&mut commands);
+        commands.queue(network_command::Disconnect);
+    });
+    app.update();
+
+    NetworkCommand::Disconnect.write_world(app.world_mut());
+
+    let connects = app.world().resource::<Messages<network_command::Connect>>();
+    let addresses: Vec<_> = connects
+        .iter_current_update_messages()
+        .map(|connect| connect.address.clone())
+        .collect();
+    assert_eq!(addresses, vec!["127.0.0.1"]);
+
+    let disconnects = app
+        .world()
+        .resource::<Messages<network_command::Disconnect>>();
+    assert_eq!(disconnects.len(), 2);
+}
@@ -0,0 +1,215 @@
+//! Tests for tuple and unit variants in `EnumEntityEvent`: positional targets and `inject_entity`.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_enum_event::EnumEntityEvent;
+use common::Log;
+
+#[derive(EnumEntityEvent, Clone, Copy, Debug, PartialEq)]
+#[allow(dead_code)]
+enum UnitEvent {
+    // The only field of a tuple variant is the target if it is an `Entity`
+    Died(Entity),
+    Healed(u32, #[enum_event(target)] Entity),
+}
+
+#[test]
+fn test_positional_targets() {
+    let mut app = App::new();
+    app.init_resource::<Log>();
+    let unit = app.world_mut().spawn_empty().id();
+    app.world_mut()
+        .entity_mut(unit)
+        .observe(|event: On<unit_event::Died>, mut log: ResMut<Log>| {
+            log.0
+                .push(format!("died {}", event.event_target() == event.0));
+        })
+        .observe(|event: On<unit_event::Healed>, mut log: ResMut<Log>| {
+            log.0.push(format!("healed {}", event.0));
+        });
+
+    UnitEvent::Died(unit).trigger_world(app.world_mut());
+    UnitEvent::Healed(4, unit).trigger_world(app.world_mut());
+
+    assert_eq!(
+        app.world().resource::<Log>().0,
+        vec!["died true", "healed 4"]
+    );
+    assert_eq!(unit_event::Healed::new(2, unit).into_parts(), (2, unit));
+}
+
+#[derive(EnumEntityEvent, Clone, Debug, PartialEq)]
+#[enum_event(inject_entity)]
+#[allow(dead_code)]
+enum PlayerEvent {
+    Selected,
+    Renamed(String),
+    Damaged { amount: u32 },
+    Moved { entity: Entity, x: f32 },
+}
+
+#[test]
+fn test_injected_entity() {
+    let mut app = App::new();
+    app.add_plugins(player_event::Plugin);
+    app.init_resource::<Log>();
+    let player = app.world_mut().spawn_empty().id();
+    app.world_mut()
+        .entity_mut(player)
+        .observe(|_event: On<player_event::Selected>, mut log: ResMut<Log>| {
+            log.0.push("selected".to_string());
+        })
+        .observe(|event: On<player_event::Renamed>, mut log: ResMut<Log>| {
+            log.0.push(format!("renamed {}", event.0));
+        })
+        .observe(|event: On<player_event::Damaged>, mut log: ResMut<Log>| {
+            log.0.push(format!("damaged {}", event.amount));
+        });
+    app.add_observer(|event: On<player_event::Any>, mut log: ResMut<Log>| {
+        log.0.push(format!(
+            "any {:?}",
+            PlayerEvent::from(event.event().clone())
+        ));
+    });
+
+    // Constructors take the injected target first
+    let selected = player_event::Selected::new(player);
+    assert_eq!(selected.entity, player);
+    app.world_mut().trigger(selected);
+    app.world_mut()
+        .trigger(player_event::Renamed::new(player, "Ada".to_string()));
+    app.world_mut().trigger(
+        player_event::Damaged::builder()
+            .amount(3)
+            .entity(player)
+            .build(),
+    );
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<Log>().0,
+        vec![
+            "selected",
+            "renamed Ada",
+            "damaged 3",
+            "any Selected",
+            "any Renamed(\"Ada\")",
+            "any Damaged { amount: 3 }",
+        ]
+    );
+}
+
+#[test]
+fn test_injected_entity_conversions() {
+    let mut world = World::new();
+    let player = world.spawn_empty().id();
+
+    // Struct to enum drops the target; the enum value has none to give back
+    let renamed = player_event::Renamed::from_parts((player, "Bo".to_string()));
+    assert_eq!(renamed.1, player);
+    assert_eq!(renamed.clone().into_parts(), (player, "Bo".to_string()));
+    assert_eq!(
+        PlayerEvent::from(renamed),
+        PlayerEvent::Renamed("Bo".to_string())
+    );
+    assert_eq!(
+        PlayerEvent::Selected.trigger_world(&mut world),
+        Err(PlayerEvent::Selected)
+    );
+    assert!(player_event::Any::try_from(PlayerEvent::Damaged { amount: 1 }).is_err());
+
+    // Variants with a target still convert both ways
+    let moved = PlayerEvent::Moved {
+        entity: player,
+        x: 1.0,
+    };
+    assert!(player_event::Moved::try_from(moved.clone()).is_ok());
+    assert!(moved.trigger_world(&mut world).is_ok());
+}
+
+#[derive(Clone, Copy, Debug)]
+struct HitInfo {
+    victim: Entity,
+    damage: u32,
+}
+
+#[derive(EnumEntityEvent, Clone, Copy, Debug)]
+#[allow(dead_code)]
+enum CombatEvent {
+    #[enum_event(target = hit.victim)]
+    Hit { hit: HitInfo },
+    #[enum_event(target = owner.unwrap_or(Entity::PLACEHOLDER))]
+    Looted { owner: Option<Entity>, gold: u32 },
+    #[enum_event(target = self.0.victim)]
+    Blocked(HitInfo),
+    // A computed target wins over the `entity` field
+    #[enum_event(target = source)]
+    Reflected { entity: Entity, source: Entity },
+}
+
+#[test]
+fn test_computed_targets() {
+    let mut app = App::new();
+    app.add_plugins(combat_event::Plugin);
+    app.init_resource::<Log>();
+    let victim = app.world_mut().spawn_empty().id();
+    let source = app.world_mut().spawn_empty().id();
+    app.world_mut()
+        .entity_mut(victim)
+        .observe(|event: On<combat_event::Hit>, mut log: ResMut<Log>| {
+            log.0.push(format!("hit {}", event.hit.damage));
+        })
+        .observe(|event: On<combat_event::Blocked>, mut log: ResMut<Log>| {
+            log.0.push(format!("blocked {}", event.0.damage));
+        })
+        .observe(
+            |_event: On<combat_event::Reflected>, mut log: ResMut<Log>| {
+                log.0.push("reflected at victim".to_string());
+            },
+        );
+    app.world_mut().entity_mut(source).observe(
+        |_event: On<combat_event::Reflected>, mut log: ResMut<Log>| {
+            log.0.push("reflected at source".to_string());
+        },
+    );
+    app.add_observer(|event: On<combat_event::Any>, mut log: ResMut<Log>| {
+        log.0.push(format!(
+            "any {}",
+            event.event_target() == Entity::PLACEHOLDER
+        ));
+    });
+
+    let hit = HitInfo { victim, damage: 4 };
+    CombatEvent::Hit { hit }.trigger_world(app.world_mut());
+    CombatEvent::Blocked(hit).trigger_world(app.world_mut());
+    CombatEvent::Reflected {
+        entity: victim,
+        source,
+    }
+    .trigger_world(app.world_mut());
+    CombatEvent::Looted {
+        owner: None,
+        gold: 3,
+    }
+    .trigger_world(app.world_mut());
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<Log>().0,
+        vec![
+            "hit 4",
+            "blocked 4",
+            "reflected at source",
+            "any false",
+            "any false",
+            "any false",
+            "any true",
+        ]
+    );
+    let looted = combat_event::Looted {
+        owner: Some(source),
+        gold: 1,
+    };
+    assert_eq!(looted.event_target(), source);
+}
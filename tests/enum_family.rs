@@ -0,0 +1,76 @@
+//! Tests for the `EnumFamily` trait implemented on every source enum.
+
+mod common;
+
+use bevy::prelude::Entity;
+use bevy_enum_event::{EnumEntityEvent, EnumFamily, EnumMessage, EventKind};
+use common::{game_event, GameEvent};
+use std::marker::PhantomData;
+
+#[test]
+fn test_family_constants() {
+    assert_eq!(
+        GameEvent::VARIANT_NAMES,
+        &["Victory", "ScoreChanged", "GameOver"]
+    );
+    assert_eq!(GameEvent::VARIANT_COUNT, 3);
+    assert_eq!(GameEvent::MODULE_PATH, "enum_family::common::game_event");
+    assert_eq!(GameEvent::EVENT_KIND, EventKind::Event);
+}
+
+#[test]
+fn test_variants_tuple() {
+    let _: PhantomData<(
+        game_event::Victory,
+        game_event::ScoreChanged,
+        game_event::GameOver,
+    )> = PhantomData::<<GameEvent as EnumFamily>::Variants>;
+}
+
+mod nested {
+    use bevy_enum_event::EnumMessage;
+
+    #[derive(EnumMessage, Clone, Debug)]
+    #[allow(dead_code)]
+    pub enum NetworkCommand {
+        Disconnect,
+    }
+}
+
+#[test]
+fn test_family_kinds_and_paths() {
+    #[derive(EnumEntityEvent, Clone, Copy, Debug)]
+    #[allow(dead_code)]
+    enum PlayerEvent {
+        Damaged { entity: Entity, amount: u32 },
+    }
+
+    assert_eq!(
+        nested::NetworkCommand::MODULE_PATH,
+        "enum_family::nested::network_command"
+    );
+    assert_eq!(nested::NetworkCommand::EVENT_KIND, EventKind::Message);
+    let _: PhantomData<(nested::network_command::Disconnect,)> =
+        PhantomData::<<nested::NetworkCommand as EnumFamily>::Variants>;
+
+    assert_eq!(PlayerEvent::EVENT_KIND, EventKind::EntityEvent);
+    assert_eq!(PlayerEvent::VARIANT_COUNT, 1);
+}
+
+#[test]
+fn test_generic_family() {
+    #[derive(EnumMessage, Clone, Debug)]
+    #[allow(dead_code)]
+    enum Sample<T: Clone + Send + Sync + 'static> {
+        Value(T),
+        Reset,
+    }
+
+    fn describe<E: EnumFamily>() -> String {
+        format!("{}[{}]", E::VARIANT_NAMES.join("|"), E::VARIANT_COUNT)
+    }
+
+    assert_eq!(describe::<Sample<u32>>(), "Value|Reset[2]");
+    let _: PhantomData<(sample::Value<u32>, sample::Reset<u32>)> =
+        PhantomData::<<Sample<u32> as EnumFamily>::Variants>;
+}
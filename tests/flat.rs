@@ -0,0 +1,181 @@
+//! Tests for flat generation, where the structs sit next to the enum instead of in a module.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_enum_event::{EnumEntityEvent, EnumEvent, EnumFamily, VariantOf};
+use common::Log;
+
+#[derive(EnumEvent, Clone, Debug, PartialEq)]
+#[enum_event(flat, suffix = "Event")]
+#[allow(dead_code)]
+enum GameEvent {
+    Victory(String),
+    ScoreChanged { team: u32, score: i32 },
+    GameOver,
+}
+
+#[test]
+fn test_flat_structs() {
+    let victory = VictoryEvent("red".to_string());
+    assert_eq!(victory.kind(), GameEventKind::Victory);
+    assert_eq!(
+        GameEvent::from(victory),
+        GameEvent::Victory("red".to_string())
+    );
+    assert!(ScoreChangedEvent::try_from(GameEvent::GameOver).is_err());
+    assert!(GameOverEvent == GameEvent::GameOver);
+
+    assert_eq!(<ScoreChangedEvent as VariantOf<GameEvent>>::INDEX, 1);
+    assert_eq!(GameEvent::MODULE_PATH, "flat");
+    assert_eq!(GameEventKind::ALL.len(), 3);
+}
+
+#[test]
+fn test_flat_observers_and_any() {
+    let mut app = App::new();
+    app.add_plugins(GameEventPlugin);
+    app.init_resource::<Log>();
+    app.add_observer(|event: On<ScoreChangedEvent>, mut log: ResMut<Log>| {
+        log.0.push(format!("score_{}", event.score));
+    });
+    app.add_observer(|event: On<GameEventAny>, mut log: ResMut<Log>| {
+        log.0.push(format!(
+            "any_{:?}",
+            GameEvent::from(event.event().clone()).kind()
+        ));
+    });
+
+    GameEvent::ScoreChanged { team: 1, score: 4 }.trigger_world(app.world_mut());
+    app.world_mut().trigger(GameOverEvent);
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<Log>().0,
+        vec!["score_4", "any_ScoreChanged", "any_GameOver"]
+    );
+}
+
+// Flat items from several enums can live side by side in one module and be re-exported
+mod events {
+    use bevy_enum_event::EnumMessage;
+
+    #[derive(EnumMessage, Clone, Debug)]
+    #[enum_event(flat, prefix = "Net")]
+    #[allow(dead_code)]
+    pub enum NetworkCommand {
+        Connect { address: String },
+        Disconnect,
+    }
+
+    #[derive(EnumMessage, Clone, Debug)]
+    #[enum_event(flat, suffix = "Request")]
+    #[allow(dead_code)]
+    pub enum SaveCommand {
+        Save(u32),
+        Load(u32),
+    }
+}
+
+mod prelude {
+    pub use super::events::{NetConnect, NetDisconnect, NetworkCommand, NetworkCommandPlugin};
+}
+
+#[test]
+fn test_flat_messages_with_reader_writer() {
+    use events::{
+        NetworkCommandReader, NetworkCommandWriter, SaveCommand, SaveCommandPlugin, SaveRequest,
+    };
+    use prelude::*;
+
+    let mut app = App::new();
+    app.add_plugins((NetworkCommandPlugin, SaveCommandPlugin));
+    app.init_resource::<Log>();
+    app.add_systems(
+        Update,
+        (
+            |mut writer: NetworkCommandWriter, mut saves: MessageWriter<SaveRequest>| {
+                writer.write(NetworkCommand::Disconnect);
+                writer.write(NetworkCommand::Connect {
+                    address: "10.0.0.1".to_string(),
+                });
+                saves.write(SaveRequest(7));
+            },
+            |mut reader: NetworkCommandReader, mut log: ResMut<Log>| {
+                for command in reader.read_in_write_order() {
+                    log.0.push(format!("{command:?}"));
+                }
+            },
+        )
+            .chain(),
+    );
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<Log>().0,
+        vec!["Disconnect", "Connect { address: \"10.0.0.1\" }"]
+    );
+    assert_eq!(app.world().resource::<Messages<SaveRequest>>().len(), 1);
+    let _ = NetConnect {
+        address: String::new(),
+    };
+    let _ = NetDisconnect;
+    assert!(matches!(
+        SaveCommand::from(SaveRequest(1)),
+        SaveCommand::Save(1)
+    ));
+}
+
+#[derive(EnumEntityEvent, Clone, Copy, Debug)]
+#[enum_event(flat, prefix, propagate)]
+#[allow(dead_code)]
+enum UiEvent {
+    Click { entity: Entity },
+}
+
+#[test]
+fn test_flat_entity_event_propagation() {
+    let mut app = App::new();
+    app.init_resource::<Log>();
+    let parent = app.world_mut().spawn_empty().id();
+    let child = app.world_mut().spawn(ChildOf(parent)).id();
+    app.world_mut()
+        .entity_mut(parent)
+        .observe(|_event: On<UiEventClick>, mut log: ResMut<Log>| {
+            log.0.push("parent".to_string());
+        });
+    app.world_mut().entity_mut(child).observe(
+        |mut event: On<UiEventClick>, mut log: ResMut<Log>| {
+            log.0.push("child".to_string());
+            event.propagate(true);
+        },
+    );
+
+    UiEvent::Click { entity: child }.trigger_world(app.world_mut());
+    assert_eq!(app.world().resource::<Log>().0, vec!["child", "parent"]);
+}
+
+#[test]
+fn test_flat_generic_enum() {
+    #[derive(EnumEvent, Clone, Debug)]
+    #[enum_event(flat, suffix = "Sample")]
+    #[allow(dead_code)]
+    enum Sample<T: Clone + Send + Sync + 'static> {
+        Value(T),
+        Reset,
+    }
+
+    let mut app = App::new();
+    app.add_plugins(SamplePlugin::<u32>::default());
+    app.init_resource::<Log>();
+    app.add_observer(|event: On<SampleAny<u32>>, mut log: ResMut<Log>| {
+        log.0
+            .push(format!("{:?}", Sample::from(event.event().clone()).kind()));
+    });
+
+    Sample::Value(1_u32).trigger_world(app.world_mut());
+    app.world_mut().trigger(ResetSample::<u32>::new());
+    app.update();
+
+    assert_eq!(app.world().resource::<Log>().0, vec!["Value", "Reset"]);
+}
@@ -0,0 +1,104 @@
+//! Tests for variant attributes and docs carried over to the generated structs.
+#![deny(deprecated)]
+
+use bevy::prelude::*;
+use bevy::reflect::{TypeInfo, Typed};
+use bevy_enum_event::{EnumEvent, EnumMessage};
+
+/// Events of a save slot.
+#[derive(EnumEvent, Clone, Debug)]
+#[allow(dead_code)]
+enum SaveEvent {
+    /// The slot was written.
+    Saved(u32),
+    #[deprecated(note = "use `Saved`")]
+    Stored(u32),
+    #[deprecated(note = "use `Saved`")]
+    #[enum_event(exclude_attrs(deprecated))]
+    Archived(u32),
+    #[allow(clippy::large_enum_variant)]
+    Cleared,
+}
+
+#[test]
+fn test_deprecated_variant() {
+    // The generated code itself doesn't trip `deny(deprecated)`; user code naming the
+    // deprecated struct does
+    #[allow(deprecated)]
+    let stored = save_event::Stored(1);
+    #[allow(deprecated)]
+    let event = SaveEvent::from(stored);
+    assert_eq!(event.kind(), save_event::Kind::Stored);
+    let _ = save_event::Saved(2);
+    let _ = save_event::Archived(3);
+}
+
+#[derive(EnumMessage, Clone, Debug, Default)]
+#[enum_event(exclude_attrs(deprecated))]
+#[allow(dead_code)]
+enum ConfigMessage {
+    // `#[default]` belongs to the enum's `Default` derive and is never forwarded
+    #[default]
+    Reload,
+    #[deprecated(note = "only the enum variant is deprecated")]
+    Legacy(String),
+    Apply {
+        key: String,
+    },
+}
+
+#[test]
+fn test_excluded_attrs() {
+    assert!(matches!(ConfigMessage::default(), ConfigMessage::Reload));
+    let _ = config_message::Reload;
+    let legacy = config_message::Legacy("old".to_string());
+    assert_eq!(legacy.0, "old");
+    let apply = config_message::Apply {
+        key: "volume".to_string(),
+    };
+    assert_eq!(apply.key, "volume");
+}
+
+#[derive(Reflect, Clone, Debug, PartialEq)]
+struct Tag(&'static str);
+
+// `reflect` belongs to the enum's `Reflect` derive unless forwarded. Paths in forwarded
+// attributes resolve from inside the module.
+#[derive(EnumEvent, Clone, Debug, Reflect)]
+#[enum_event(derive(Reflect))]
+#[allow(dead_code)]
+enum Marker {
+    #[reflect(@crate::Tag("enum"))]
+    Placed,
+    #[reflect(@crate::Tag("both"))]
+    #[enum_event(forward_attrs(reflect))]
+    Removed,
+}
+
+#[test]
+fn test_forward_attrs() {
+    let custom = |info: &TypeInfo| {
+        let info = info.as_struct().unwrap();
+        info.custom_attributes().get::<Tag>().cloned()
+    };
+    assert_eq!(custom(marker::Placed::type_info()), None);
+    assert_eq!(custom(marker::Removed::type_info()), Some(Tag("both")));
+}
+
+// The struct doesn't derive `Reflect`, so a forwarded `#[reflect]` would fail to compile
+#[derive(EnumEvent, Clone, Debug, Reflect)]
+#[allow(dead_code)]
+enum Cursor {
+    #[reflect(@crate::Tag("enum"))]
+    Moved(u32),
+}
+
+#[test]
+fn test_unlisted_attrs_are_dropped() {
+    let TypeInfo::Enum(info) = Cursor::type_info() else {
+        panic!("`Cursor` is not an enum");
+    };
+    let variant = info.variant("Moved").unwrap();
+    assert_eq!(variant.custom_attributes().get::<Tag>(), Some(&Tag("enum")));
+    assert_eq!(cursor::Moved(4).0, 4);
+}
@@ -0,0 +1,192 @@
+//! Tests for path resolution in generated modules, which import nothing from the enum's scope.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_enum_event::{EnumEvent, EnumMessage};
+use common::Log;
+
+// Local types named like Bevy's, shadowing the prelude glob
+#[derive(Clone, Debug, PartialEq)]
+struct Event(u32);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Entity(u8);
+
+#[derive(Clone, Debug, PartialEq)]
+struct Message(String);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Deref;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Position(f32, f32);
+
+const START: u32 = 10;
+
+mod units {
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Meters(pub f32);
+}
+
+#[derive(EnumEvent, Clone, Debug, PartialEq)]
+#[allow(dead_code)]
+enum Mail {
+    Sent(Message),
+    Logged {
+        event: Event,
+        #[enum_event(default = START)]
+        count: u32,
+    },
+    Stamped {
+        entity: Entity,
+        marks: Vec<Deref>,
+    },
+    Moved(self::units::Meters),
+    // The struct would have shadowed the field type under a glob import
+    Position(Position),
+}
+
+#[test]
+fn test_parent_types() {
+    let mut app = App::new();
+    app.add_plugins(mail::Plugin);
+    app.init_resource::<Log>();
+    app.add_observer(|event: On<mail::Sent>, mut log: ResMut<Log>| {
+        log.0.push(format!("sent {}", event.0 .0));
+    });
+    app.add_observer(|event: On<mail::Any>, mut log: ResMut<Log>| {
+        log.0
+            .push(format!("{:?}", Mail::from(event.event().clone())));
+    });
+
+    Mail::Sent(Message("hi".to_string())).trigger_world(app.world_mut());
+    app.world_mut()
+        .trigger(mail::Logged::builder().event(Event(2)).build());
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<Log>().0,
+        vec![
+            "sent hi",
+            "Sent(Message(\"hi\"))",
+            "Logged { event: Event(2), count: 10 }",
+        ]
+    );
+    let stamped = mail::Stamped::new(Entity(1), vec![Deref]);
+    assert_eq!(stamped.into_parts(), (Entity(1), vec![Deref]));
+    assert_eq!(mail::Moved(units::Meters(2.0)).0, units::Meters(2.0));
+    assert_eq!(
+        Mail::from(mail::Position(Position(1.0, 2.0))),
+        Mail::Position(Position(1.0, 2.0))
+    );
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct JobError;
+
+// Shadows the prelude's `Result`, and takes one parameter instead of two
+type Result<T> = std::result::Result<T, JobError>;
+
+#[derive(EnumMessage, Clone, Debug, PartialEq)]
+#[allow(dead_code)]
+enum Job {
+    Done(Result<u32>),
+    Failed { error: Result<()> },
+}
+
+#[test]
+fn test_parent_aliases() {
+    let done: Result<u32> = job::Done(Ok(3)).0;
+    assert_eq!(done, Ok(3));
+    assert_eq!(
+        job::Failed::new(Err(JobError)),
+        Job::Failed {
+            error: Err(JobError)
+        }
+    );
+
+    let mut app = App::new();
+    app.add_plugins(job::Plugin);
+    app.init_resource::<Log>();
+    app.add_systems(
+        Update,
+        (
+            |mut writer: job::Writer| {
+                writer.write(Job::Done(Err(JobError)));
+            },
+            |mut reader: job::Reader, mut log: ResMut<Log>| {
+                for job in reader.read_in_write_order() {
+                    log.0.push(format!("{job:?}"));
+                }
+            },
+        )
+            .chain(),
+    );
+    app.update();
+
+    assert_eq!(app.world().resource::<Log>().0, vec!["Done(Err(JobError))"]);
+}
+
+trait Weight {
+    fn weight(&self) -> u32;
+}
+
+impl Weight for u32 {
+    fn weight(&self) -> u32 {
+        *self
+    }
+}
+
+#[derive(EnumMessage, Clone, Debug)]
+#[allow(dead_code)]
+enum Cargo<T: Weight + Clone + Send + Sync + 'static> {
+    Loaded {
+        item: T,
+        #[enum_event(default = START + 1)]
+        count: u32,
+    },
+    Unloaded,
+}
+
+#[test]
+fn test_parent_bounds_and_defaults() {
+    let loaded = cargo::Loaded::builder().item(3_u32).build();
+    assert_eq!((loaded.item.weight(), loaded.count), (3, 11));
+}
+
+mod nested {
+    use bevy_enum_event::EnumEntityEvent;
+
+    #[derive(EnumEntityEvent, Clone, Debug)]
+    #[allow(dead_code)]
+    pub enum Alarm {
+        Rang {
+            entity: bevy::ecs::entity::Entity,
+            source: super::Entity,
+            note: Option<super::Message>,
+        },
+    }
+}
+
+#[test]
+fn test_super_paths() {
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    let target = world.spawn_empty().id();
+    world
+        .entity_mut(target)
+        .observe(|event: On<nested::alarm::Rang>, mut log: ResMut<Log>| {
+            log.0.push(format!("rang {:?}", event.source));
+        });
+
+    nested::Alarm::Rang {
+        entity: target,
+        source: Entity(7),
+        note: None,
+    }
+    .trigger_world(&mut world);
+    world.flush();
+
+    assert_eq!(world.resource::<Log>().0, vec!["rang Entity(7)"]);
+}
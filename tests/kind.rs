@@ -0,0 +1,106 @@
+//! Tests for the generated payload-free `Kind` enum.
+
+mod common;
+
+use bevy::prelude::Entity;
+use bevy_enum_event::{EnumEntityEvent, EnumEvent, EnumMessage};
+use common::{game_event, GameEvent};
+use std::collections::HashMap;
+
+#[test]
+fn test_kind_of_enum_and_structs() {
+    assert_eq!(
+        GameEvent::Victory("red".to_string()).kind(),
+        game_event::Kind::Victory
+    );
+    assert_eq!(
+        GameEvent::ScoreChanged { team: 1, score: 2 }.kind(),
+        game_event::Kind::ScoreChanged
+    );
+    assert_eq!(GameEvent::GameOver.kind(), game_event::Kind::GameOver);
+
+    assert_eq!(
+        game_event::Victory("red".to_string()).kind(),
+        game_event::Kind::Victory
+    );
+    assert_eq!(
+        game_event::ScoreChanged { team: 1, score: 2 }.kind(),
+        game_event::Kind::ScoreChanged
+    );
+    assert_eq!(game_event::GameOver.kind(), game_event::Kind::GameOver);
+}
+
+#[test]
+fn test_kind_all_and_name() {
+    let names: Vec<_> = game_event::Kind::ALL
+        .iter()
+        .map(|kind| kind.name())
+        .collect();
+    assert_eq!(names, vec!["Victory", "ScoreChanged", "GameOver"]);
+
+    const GAME_OVER: &str = game_event::Kind::GameOver.name();
+    assert_eq!(GAME_OVER, "GameOver");
+}
+
+#[test]
+fn test_kind_as_counter_key() {
+    let events = [
+        GameEvent::GameOver,
+        GameEvent::Victory("red".to_string()),
+        GameEvent::GameOver,
+    ];
+
+    let mut counts = HashMap::new();
+    for event in &events {
+        *counts.entry(event.kind()).or_insert(0) += 1;
+    }
+
+    assert_eq!(counts[&game_event::Kind::GameOver], 2);
+    assert_eq!(counts[&game_event::Kind::Victory], 1);
+    assert!(!counts.contains_key(&game_event::Kind::ScoreChanged));
+}
+
+#[test]
+fn test_kind_keeps_discriminants() {
+    #[derive(EnumMessage, Clone, Debug)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum Opcode {
+        Ping = 3,
+        Pong,
+        Data(Vec<u8>) = 10,
+    }
+
+    assert_eq!(opcode::Kind::Ping as u8, 3);
+    assert_eq!(opcode::Kind::Pong as u8, 4);
+    assert_eq!(opcode::Kind::Data as u8, 10);
+    assert_eq!(Opcode::Data(vec![1]).kind() as u8, 10);
+    assert_eq!(std::mem::size_of::<opcode::Kind>(), 1);
+}
+
+#[test]
+fn test_kind_generic_and_entity_events() {
+    #[derive(EnumEvent, Clone, Debug)]
+    #[allow(dead_code)]
+    enum Sample<T: Clone + Send + Sync + 'static> {
+        Value(T),
+        Reset,
+    }
+
+    #[derive(EnumEntityEvent, Clone, Copy, Debug)]
+    #[allow(dead_code)]
+    enum PlayerEvent {
+        Damaged { entity: Entity, amount: u32 },
+        Healed { entity: Entity, amount: u32 },
+    }
+
+    assert_eq!(Sample::Value(1_u32).kind(), sample::Kind::Value);
+    assert_eq!(sample::Reset::<u32>::new().kind(), sample::Kind::Reset);
+
+    let healed = player_event::Healed {
+        entity: Entity::PLACEHOLDER,
+        amount: 1,
+    };
+    assert_eq!(healed.kind(), player_event::Kind::Healed);
+    assert_eq!(player_event::Kind::ALL.len(), 2);
+}
@@ -0,0 +1,226 @@
+//! Tests for variant-level `kind = message | event | entity` overrides.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_enum_event::{EnumEntityEvent, EnumEvent, EnumFamily, EnumMessage, EventKind};
+use common::Log;
+
+#[derive(EnumEvent, Clone, Debug, PartialEq)]
+#[allow(dead_code)]
+enum CombatEvent {
+    RoundStarted(u32),
+    #[enum_event(kind = message)]
+    DamageDealt {
+        amount: u32,
+    },
+    #[enum_event(kind = message)]
+    Missed,
+    #[enum_event(kind = entity)]
+    Hit {
+        entity: Entity,
+        amount: u32,
+    },
+}
+
+#[test]
+fn test_mixed_family_items() {
+    assert_eq!(CombatEvent::EVENT_KIND, EventKind::Event);
+    assert_eq!(CombatEvent::VARIANT_COUNT, 4);
+    assert!(matches!(
+        combat_event::Any::try_from(CombatEvent::Missed),
+        Err(CombatEvent::Missed)
+    ));
+    assert_eq!(
+        combat_event::Kind::ALL,
+        [
+            combat_event::Kind::RoundStarted,
+            combat_event::Kind::DamageDealt,
+            combat_event::Kind::Missed,
+            combat_event::Kind::Hit,
+        ]
+    );
+}
+
+#[test]
+fn test_mixed_dispatch() {
+    let mut app = App::new();
+    app.add_plugins(combat_event::Plugin);
+    app.init_resource::<Log>();
+    let target = app.world_mut().spawn_empty().id();
+    app.add_observer(|event: On<combat_event::Any>, mut log: ResMut<Log>| {
+        log.0.push(format!(
+            "any {:?}",
+            CombatEvent::from(event.event().clone())
+        ));
+    });
+    app.world_mut().entity_mut(target).observe(
+        |event: On<combat_event::Hit>, mut log: ResMut<Log>| {
+            log.0.push(format!("hit {}", event.amount));
+        },
+    );
+
+    // Events are triggered, messages written; each hands the other kind back
+    assert!(CombatEvent::RoundStarted(1)
+        .trigger_world(app.world_mut())
+        .is_ok());
+    assert_eq!(
+        CombatEvent::Missed.trigger_world(app.world_mut()),
+        Err(CombatEvent::Missed)
+    );
+    assert!(CombatEvent::Missed.write_world(app.world_mut()).is_ok());
+    assert!(CombatEvent::RoundStarted(2)
+        .write_world(app.world_mut())
+        .is_err());
+    CombatEvent::Hit {
+        entity: target,
+        amount: 7,
+    }
+    .trigger_world(app.world_mut())
+    .unwrap();
+    app.world_mut()
+        .commands()
+        .queue(combat_event::DamageDealt { amount: 3 });
+    app.update();
+
+    // Forwarding to `Any` goes through commands, so it runs after the direct observers
+    assert_eq!(
+        app.world().resource::<Log>().0,
+        vec![
+            "hit 7".to_string(),
+            "any RoundStarted(1)".to_string(),
+            format!("any Hit {{ entity: {target:?}, amount: 7 }}"),
+        ]
+    );
+    assert_eq!(
+        app.world()
+            .resource::<Messages<combat_event::Missed>>()
+            .len(),
+        1
+    );
+    assert_eq!(
+        app.world()
+            .resource::<Messages<combat_event::DamageDealt>>()
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn test_mixed_reader_writer() {
+    let mut app = App::new();
+    app.add_plugins(combat_event::Plugin);
+    app.init_resource::<Log>();
+    app.add_systems(
+        Update,
+        (
+            |mut writer: combat_event::Writer| {
+                writer
+                    .write(CombatEvent::DamageDealt { amount: 4 })
+                    .unwrap();
+                assert!(writer.write(CombatEvent::RoundStarted(1)).is_err());
+                writer.write(CombatEvent::Missed).unwrap();
+            },
+            |mut reader: combat_event::Reader, mut log: ResMut<Log>| {
+                for event in reader.read_in_write_order() {
+                    log.0.push(format!("{event:?}"));
+                }
+            },
+        )
+            .chain(),
+    );
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<Log>().0,
+        vec!["DamageDealt { amount: 4 }", "Missed"]
+    );
+}
+
+// Entity-field validation only applies to the entity variants
+#[derive(EnumEntityEvent, Clone, Copy, Debug)]
+#[enum_event(auto_propagate, propagate)]
+#[allow(dead_code)]
+enum UnitEvent {
+    Selected {
+        entity: Entity,
+    },
+    #[enum_event(kind = event)]
+    SelectionCleared,
+    #[enum_event(kind = message)]
+    Moved(Vec2),
+}
+
+#[test]
+fn test_entity_family_with_global_variants() {
+    let mut app = App::new();
+    app.add_plugins(unit_event::Plugin);
+    app.init_resource::<Log>();
+    let parent = app.world_mut().spawn_empty().id();
+    let child = app.world_mut().spawn(ChildOf(parent)).id();
+    app.world_mut().entity_mut(parent).observe(
+        |_event: On<unit_event::Selected>, mut log: ResMut<Log>| {
+            log.0.push("parent selected".to_string());
+        },
+    );
+    app.add_observer(|event: On<unit_event::Any>, mut log: ResMut<Log>| {
+        log.0.push(format!(
+            "any {:?}",
+            UnitEvent::from(event.event().clone()).kind()
+        ));
+    });
+
+    // Propagation applies to the entity variant only; `Any` is forwarded at every hop
+    UnitEvent::Selected { entity: child }
+        .trigger_world(app.world_mut())
+        .unwrap();
+    app.world_mut().trigger(unit_event::SelectionCleared);
+    UnitEvent::Moved(Vec2::X)
+        .write_world(app.world_mut())
+        .unwrap();
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<Log>().0,
+        vec![
+            "parent selected",
+            "any Selected",
+            "any Selected",
+            "any SelectionCleared"
+        ]
+    );
+    assert_eq!(
+        app.world().resource::<Messages<unit_event::Moved>>().len(),
+        1
+    );
+    assert_eq!(UnitEvent::EVENT_KIND, EventKind::EntityEvent);
+}
+
+#[derive(EnumMessage, Clone, Debug)]
+#[enum_event(flat, suffix = "Signal")]
+#[allow(dead_code)]
+enum Signal {
+    Tick(u32),
+    #[enum_event(kind = event)]
+    Alarm,
+}
+
+#[test]
+fn test_flat_mixed_family() {
+    let mut app = App::new();
+    app.add_plugins(SignalPlugin);
+    app.init_resource::<Log>();
+    app.add_observer(|_event: On<AlarmSignal>, mut log: ResMut<Log>| {
+        log.0.push("alarm".to_string());
+    });
+
+    Signal::Tick(1).write_world(app.world_mut()).unwrap();
+    Signal::Alarm.trigger_world(app.world_mut()).unwrap();
+
+    assert_eq!(app.world().resource::<Log>().0, vec!["alarm"]);
+    assert_eq!(app.world().resource::<Messages<TickSignal>>().len(), 1);
+    assert!(matches!(
+        SignalAny::try_from(Signal::Alarm),
+        Ok(SignalAny::Alarm(_))
+    ));
+}
@@ -0,0 +1,240 @@
+//! Tests for configurable module and struct names.
+
+use bevy::prelude::*;
+use bevy_enum_event::{EnumEntityEvent, EnumEvent, EnumFamily, EnumMessage, VariantOf};
+
+// `HTTPServer` and `HttpServer` both snake-case to `http_server`
+#[derive(EnumEvent, Clone, Debug)]
+#[allow(dead_code, clippy::upper_case_acronyms)]
+enum HTTPServer {
+    Started,
+}
+
+#[derive(EnumEvent, Clone, Debug)]
+#[enum_event(module = "http_server_events")]
+#[allow(dead_code)]
+enum HttpServer {
+    Started,
+    Stopped,
+}
+
+#[test]
+fn test_module_override() {
+    let _: http_server::Started = http_server::Started;
+    let _: http_server_events::Stopped = http_server_events::Stopped;
+    assert_eq!(HttpServer::MODULE_PATH, "naming::http_server_events");
+}
+
+// A variant sharing its name with a type used in the fields needs a rename
+#[derive(Clone, Debug, PartialEq)]
+pub struct Score(u32);
+
+#[derive(EnumEvent, Clone, Debug, PartialEq)]
+#[allow(dead_code)]
+enum ScoreEvent {
+    #[enum_event(rename = "ScoreReached")]
+    Score(Score),
+    Reset,
+}
+
+#[test]
+fn test_variant_rename() {
+    let reached = score_event::ScoreReached(Score(3));
+    assert_eq!(reached.0, Score(3));
+
+    let event: ScoreEvent = reached.into();
+    assert_eq!(event, ScoreEvent::Score(Score(3)));
+    assert_eq!(event.kind(), score_event::Kind::Score);
+    assert_eq!(
+        <score_event::ScoreReached as VariantOf<ScoreEvent>>::NAME,
+        "Score"
+    );
+    assert_eq!(ScoreEvent::VARIANT_NAMES, &["Score", "Reset"]);
+}
+
+#[derive(EnumEvent, Clone, Debug)]
+#[enum_event(prefix)]
+#[allow(dead_code)]
+enum GameEvent {
+    Victory(String),
+    GameOver,
+}
+
+#[derive(EnumMessage, Clone, Debug)]
+#[enum_event(prefix = "Net", suffix = "Command")]
+#[allow(dead_code)]
+enum NetworkCommand {
+    Connect {
+        address: String,
+    },
+    #[enum_event(rename = "Hangup")]
+    Disconnect,
+}
+
+#[test]
+fn test_prefix_and_suffix() {
+    let _: game_event::GameEventVictory = game_event::GameEventVictory("red".to_string());
+    let _: game_event::GameEventGameOver = game_event::GameEventGameOver;
+
+    let connect = network_command::NetConnectCommand {
+        address: "127.0.0.1".to_string(),
+    };
+    assert!(matches!(
+        NetworkCommand::from(connect),
+        NetworkCommand::Connect { .. }
+    ));
+    // `rename` gives the full struct name, without prefix or suffix
+    let _: network_command::Hangup = network_command::Hangup;
+}
+
+#[test]
+fn test_renamed_structs_through_generated_items() {
+    let mut app = App::new();
+    app.add_plugins(game_event::Plugin);
+    app.init_resource::<Log>();
+    app.add_observer(|event: On<game_event::Any>, mut log: ResMut<Log>| {
+        log.0.push(event.event().clone().into());
+    });
+    GameEvent::Victory("red".to_string()).trigger_world(app.world_mut());
+    app.world_mut().trigger(game_event::GameEventGameOver);
+    app.update();
+
+    let kinds: Vec<_> = app
+        .world()
+        .resource::<Log>()
+        .0
+        .iter()
+        .map(GameEvent::kind)
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![game_event::Kind::Victory, game_event::Kind::GameOver]
+    );
+
+    let mut app = App::new();
+    app.add_plugins(network_command::Plugin);
+    app.add_systems(Update, |mut writer: network_command::Writer| {
+        writer.write(NetworkCommand::Disconnect);
+    });
+    app.update();
+    assert_eq!(
+        app.world()
+            .resource::<Messages<network_command::Hangup>>()
+            .len(),
+        1
+    );
+}
+
+#[derive(Resource, Default)]
+struct Log(Vec<GameEvent>);
+
+#[derive(EnumEntityEvent, Clone, Copy, Debug)]
+#[enum_event(propagate = &'static ChildOf, suffix = "Event")]
+#[allow(dead_code)]
+enum UiEvent {
+    Click { entity: Entity },
+}
+
+#[test]
+fn test_suffix_with_propagation() {
+    let mut app = App::new();
+    let parent = app.world_mut().spawn_empty().id();
+    let child = app.world_mut().spawn(ChildOf(parent)).id();
+    app.init_resource::<Clicks>();
+    app.world_mut().entity_mut(parent).observe(
+        |_event: On<ui_event::ClickEvent>, mut clicks: ResMut<Clicks>| {
+            clicks.0 += 1;
+        },
+    );
+    app.world_mut()
+        .entity_mut(child)
+        .observe(|mut event: On<ui_event::ClickEvent>| event.propagate(true));
+
+    app.world_mut()
+        .trigger(ui_event::ClickEvent { entity: child });
+    assert_eq!(app.world().resource::<Clicks>().0, 1);
+}
+
+#[derive(Resource, Default)]
+struct Clicks(u32);
+
+// `Result` is free in the module; `Reader` needs the family reader to move aside
+#[derive(EnumMessage, Clone, Debug, PartialEq)]
+#[enum_event(rename_items(reader = "QueryReader"))]
+#[allow(dead_code)]
+enum Query {
+    Result(u32),
+    Reader { name: String },
+}
+
+#[test]
+fn test_rename_items() {
+    let mut app = App::new();
+    app.add_plugins(query::Plugin);
+    app.add_systems(
+        Update,
+        (
+            |mut writer: query::Writer| {
+                writer.write(Query::Result(1));
+                writer.write(Query::Reader {
+                    name: "log".to_string(),
+                });
+            },
+            |mut reader: query::QueryReader, mut messages: MessageReader<query::Reader>| {
+                assert_eq!(
+                    reader.read_in_write_order().collect::<Vec<_>>(),
+                    vec![
+                        Query::Result(1),
+                        Query::Reader {
+                            name: "log".to_string()
+                        }
+                    ]
+                );
+                assert_eq!(messages.read().count(), 1);
+            },
+        )
+            .chain(),
+    );
+    app.update();
+    assert_eq!(query::Result(2).0, 2);
+}
+
+#[derive(EnumEvent, Clone, Debug, PartialEq)]
+#[enum_event(rename_items(plugin = "FilterPlugin", kind = "FilterKind", any = "AnyFilter"))]
+#[allow(dead_code)]
+enum Filter {
+    Any,
+    Kind(u8),
+    Plugin { name: String },
+}
+
+#[derive(Resource, Default)]
+struct FilterLog(Vec<Filter>);
+
+#[test]
+fn test_rename_family_items() {
+    assert_eq!(filter::Kind(2).kind(), filter::FilterKind::Kind);
+    assert_eq!(
+        filter::FilterKind::ALL,
+        [
+            filter::FilterKind::Any,
+            filter::FilterKind::Kind,
+            filter::FilterKind::Plugin
+        ]
+    );
+
+    let mut app = App::new();
+    app.add_plugins(filter::FilterPlugin);
+    app.init_resource::<FilterLog>();
+    app.add_observer(|event: On<filter::AnyFilter>, mut log: ResMut<FilterLog>| {
+        log.0.push(event.event().clone().into());
+    });
+    Filter::Any.trigger_world(app.world_mut());
+    app.world_mut().trigger(filter::Kind(2));
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<FilterLog>().0,
+        vec![Filter::Any, Filter::Kind(2)]
+    );
+}
@@ -0,0 +1,177 @@
+//! Tests for `#[enum_event(skip)]`, which keeps a variant out of generation.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_enum_event::{EnumEntityEvent, EnumEvent, EnumFamily, EnumMessage, VariantOf};
+use common::Log;
+use std::any::Any;
+
+// Skipped payloads don't need to satisfy the struct derives or Bevy's bounds
+#[derive(EnumEvent, Debug)]
+#[allow(dead_code)]
+enum GameEvent {
+    Victory(String),
+    #[enum_event(skip)]
+    Custom(Box<dyn Any + Send + Sync>),
+    GameOver,
+    #[enum_event(skip)]
+    __Reserved,
+}
+
+#[test]
+fn test_skipped_variants_are_left_out() {
+    let _: std::marker::PhantomData<(game_event::Victory, game_event::GameOver)> =
+        std::marker::PhantomData::<<GameEvent as EnumFamily>::Variants>;
+
+    // Names, count and the kind enum still mirror the whole enum
+    assert_eq!(
+        GameEvent::VARIANT_NAMES,
+        &["Victory", "Custom", "GameOver", "__Reserved"]
+    );
+    assert_eq!(GameEvent::VARIANT_COUNT, 4);
+    assert_eq!(game_event::Kind::ALL.len(), 4);
+    assert_eq!(GameEvent::__Reserved.kind(), game_event::Kind::__Reserved);
+    assert_eq!(game_event::GameOver.kind(), game_event::Kind::GameOver);
+}
+
+#[test]
+fn test_conversions_reject_skipped_variants() {
+    let custom = GameEvent::Custom(Box::new(3_u32));
+    let Err(custom) = game_event::Victory::try_from(custom) else {
+        panic!("a skipped variant converted to a struct");
+    };
+    let Err(GameEvent::Custom(payload)) = game_event::Any::try_from(custom) else {
+        panic!("a skipped variant converted to `Any`");
+    };
+    assert_eq!(payload.downcast_ref::<u32>(), Some(&3));
+
+    assert!(matches!(
+        game_event::Any::try_from(GameEvent::GameOver),
+        Ok(game_event::Any::GameOver(_))
+    ));
+}
+
+#[test]
+fn test_dispatch_returns_skipped_values() {
+    let mut app = App::new();
+    app.add_plugins(game_event::Plugin);
+    app.init_resource::<Log>();
+    app.add_observer(|event: On<game_event::Any>, mut log: ResMut<Log>| {
+        log.0
+            .push(format!("{:?}", GameEvent::from(event.event().clone())));
+    });
+
+    assert!(GameEvent::Victory("red".to_string())
+        .trigger_world(app.world_mut())
+        .is_ok());
+    let rejected = GameEvent::__Reserved.trigger_world(app.world_mut());
+    assert!(matches!(rejected, Err(GameEvent::__Reserved)));
+    app.update();
+
+    assert_eq!(app.world().resource::<Log>().0, vec!["Victory(\"red\")"]);
+}
+
+#[derive(EnumMessage, Clone, Debug, PartialEq)]
+#[allow(dead_code)]
+enum NetworkCommand {
+    Connect {
+        address: String,
+    },
+    #[enum_event(skip)]
+    Internal(u64),
+    Disconnect,
+}
+
+#[test]
+fn test_writer_returns_skipped_values() {
+    let mut app = App::new();
+    app.add_plugins(network_command::Plugin);
+    app.init_resource::<Log>();
+    app.add_systems(
+        Update,
+        (
+            |mut writer: network_command::Writer, mut log: ResMut<Log>| {
+                writer.write(NetworkCommand::Disconnect).unwrap();
+                if let Err(command) = writer.write(NetworkCommand::Internal(9)) {
+                    log.0.push(format!("rejected {command:?}"));
+                }
+            },
+            |mut reader: network_command::Reader, mut log: ResMut<Log>| {
+                for command in reader.read_in_write_order() {
+                    log.0.push(format!("{command:?}"));
+                }
+            },
+        )
+            .chain(),
+    );
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<Log>().0,
+        vec!["rejected Internal(9)", "Disconnect"]
+    );
+    assert_eq!(
+        NetworkCommand::Internal(1).write_world(app.world_mut()),
+        Err(NetworkCommand::Internal(1))
+    );
+}
+
+// Skipped variants don't need a target entity
+#[derive(EnumEntityEvent, Clone, Copy, Debug)]
+#[allow(dead_code)]
+enum PlayerEvent {
+    Damaged {
+        entity: Entity,
+        amount: u32,
+    },
+    #[enum_event(skip)]
+    Bookkeeping,
+}
+
+#[test]
+fn test_entity_event_with_skipped_variant() {
+    let mut app = App::new();
+    app.init_resource::<Log>();
+    let player = app.world_mut().spawn_empty().id();
+    app.world_mut().entity_mut(player).observe(
+        |event: On<player_event::Damaged>, mut log: ResMut<Log>| {
+            log.0.push(format!("damaged_{}", event.amount));
+        },
+    );
+
+    PlayerEvent::Damaged {
+        entity: player,
+        amount: 5,
+    }
+    .trigger_world(app.world_mut())
+    .unwrap();
+    assert!(PlayerEvent::Bookkeeping
+        .trigger_world(app.world_mut())
+        .is_err());
+
+    assert_eq!(app.world().resource::<Log>().0, vec!["damaged_5"]);
+    assert_eq!(PlayerEvent::VARIANT_COUNT, 2);
+}
+
+#[derive(EnumEvent, Clone, Debug)]
+#[allow(dead_code)]
+enum Skipped {
+    #[enum_event(skip)]
+    A,
+    B,
+    C(u32),
+}
+
+fn kind_at_index<V: VariantOf<Skipped>>() -> skipped::Kind {
+    skipped::Kind::ALL[V::INDEX]
+}
+
+#[test]
+fn test_index_counts_skipped_variants() {
+    assert_eq!(<skipped::B as VariantOf<Skipped>>::INDEX, 1);
+    assert_eq!(<skipped::C as VariantOf<Skipped>>::INDEX, 2);
+    assert_eq!(kind_at_index::<skipped::B>(), skipped::B.kind());
+    assert_eq!(kind_at_index::<skipped::C>(), skipped::C(7).kind());
+    assert_eq!(Skipped::VARIANT_COUNT, skipped::Kind::ALL.len());
+}
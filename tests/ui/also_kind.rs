@@ -0,0 +1,17 @@
+use bevy_enum_event::{EnumEvent, EnumMessage};
+
+#[derive(EnumEvent, Clone)]
+#[enum_event(also = entity)]
+enum GameEvent {
+    Started,
+}
+
+#[derive(EnumMessage, Clone)]
+enum Network {
+    #[enum_event(kind = event, also = event)]
+    Ping,
+    #[enum_event(kind = signal)]
+    Pong,
+}
+
+fn main() {}
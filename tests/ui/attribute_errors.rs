@@ -0,0 +1,16 @@
+use bevy_enum_event::EnumEvent;
+
+// Mistakes in different variants and fields are all reported at once
+#[derive(EnumEvent, Clone)]
+enum GameEvent {
+    #[enum_event(rename = 42)]
+    Started,
+    Scored {
+        #[enum_event(vis = pub)]
+        points: u32,
+    },
+    #[enum_event(also)]
+    Ended,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use bevy_enum_event::EnumEvent;
+
+#[derive(EnumEvent, Clone)]
+#[enum_event(deref)]
+enum GameEvent {
+    Scored {
+        #[enum_event(deref)]
+        points: u32,
+        #[enum_event(deref)]
+        team: u8,
+    },
+    #[enum_event(deref)]
+    Chat(u32, String),
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+use bevy_enum_event::{EnumEntityEvent, EnumEvent};
+
+#[derive(EnumEntityEvent, Clone)]
+enum UnitEvent {
+    Damaged {
+        amount: u32,
+    },
+    Healed {
+        entity: Entity,
+        #[enum_event(target)]
+        healer: Entity,
+    },
+    #[enum_event(target = owner)]
+    Looted {
+        owner: Entity,
+        #[enum_event(target)]
+        thief: Entity,
+    },
+    #[enum_event(target = owner, propagate)]
+    Dropped {
+        owner: Entity,
+    },
+}
+
+#[derive(EnumEvent, Clone)]
+enum GameEvent {
+    #[enum_event(target = player)]
+    Joined { player: Entity },
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use bevy_enum_event::EnumEvent;
+
+#[derive(EnumEvent, Clone)]
+#[enum_event(flat, module = "events")]
+enum GameEvent {
+    Started,
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+use bevy_enum_event::EnumEvent;
+
+#[derive(EnumEvent, Clone)]
+enum GameEvent {
+    Scored {
+        #[enum_event(deref)]
+        player: Entity,
+        points: u32,
+    },
+    // An explicit policy still applies without the feature
+    #[enum_event(deref)]
+    Joined {
+        #[enum_event(deref)]
+        player: Entity,
+        team: u8,
+    },
+}
+
+fn main() {}
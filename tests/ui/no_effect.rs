@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+use bevy_enum_event::{EnumEvent, EnumMessage};
+
+#[derive(EnumEvent, Clone)]
+#[enum_event(deref = false)]
+enum GameEvent {
+    Joined {
+        #[enum_event(target)]
+        player: Entity,
+    },
+    Chat {
+        #[enum_event(deref)]
+        text: String,
+        from: u32,
+    },
+    #[enum_event(skip, rename = "Halted")]
+    Stopped,
+    #[enum_event(deref)]
+    Paused,
+}
+
+#[derive(EnumMessage, Clone)]
+enum Network {
+    Ping(#[enum_event(default)] u32),
+    Sent {
+        #[enum_event(default)]
+        bytes: u32,
+        build: u32,
+    },
+}
+
+fn main() {}
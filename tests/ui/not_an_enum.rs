@@ -0,0 +1,8 @@
+use bevy_enum_event::EnumEvent;
+
+#[derive(EnumEvent, Clone)]
+struct Scored {
+    points: u32,
+}
+
+fn main() {}
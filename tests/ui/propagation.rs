@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+use bevy_enum_event::{EnumEntityEvent, EnumEvent, EnumMessage};
+
+#[derive(EnumEvent, Clone)]
+#[enum_event(propagate)]
+enum GameEvent {
+    Started,
+}
+
+#[derive(EnumMessage, Clone)]
+enum Network {
+    #[enum_event(propagate)]
+    Connected,
+}
+
+#[derive(EnumEntityEvent, Clone)]
+#[enum_event(auto_propagate)]
+enum UiEvent {
+    #[enum_event(auto_propagate)]
+    Clicked { entity: Entity },
+}
+
+fn main() {}
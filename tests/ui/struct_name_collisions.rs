@@ -0,0 +1,29 @@
+use bevy_enum_event::{EnumEvent, EnumMessage};
+
+#[derive(Clone)]
+struct Position(f32, f32);
+
+#[derive(EnumEvent, Clone)]
+enum GameEvent {
+    Started,
+    #[enum_event(rename = "Started")]
+    Restarted,
+    Plugin,
+    // Fine inside the module, which names the field type through a `__scope` alias
+    Position(Position),
+}
+
+#[derive(EnumEvent, Clone)]
+#[enum_event(flat)]
+enum MoveEvent {
+    Position(Position),
+}
+
+#[derive(EnumMessage, Clone)]
+enum Query {
+    // Fine, the module's own imports don't reserve names
+    Result(u32),
+    Reader,
+}
+
+fn main() {}
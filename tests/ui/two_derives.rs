@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+use bevy_enum_event::{EnumEntityEvent, EnumEvent, EnumMessage};
+
+#[derive(EnumEvent, EnumMessage, Clone)]
+enum GameEvent {
+    Started,
+    Scored(u32),
+}
+
+#[derive(EnumEvent, EnumEntityEvent, Clone)]
+enum UnitEvent {
+    Spawned { entity: Entity },
+}
+
+fn main() {}
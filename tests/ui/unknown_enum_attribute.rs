@@ -0,0 +1,15 @@
+use bevy_enum_event::{EnumEvent, EnumMessage};
+
+#[derive(EnumEvent, Clone)]
+#[enum_event(propagte)]
+enum GameEvent {
+    Started,
+}
+
+#[derive(EnumMessage, Clone)]
+#[enum_event(rename_items(reeder = "QueryReader"))]
+enum Query {
+    Done,
+}
+
+fn main() {}
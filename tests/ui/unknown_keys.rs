@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+use bevy_enum_event::EnumEntityEvent;
+
+#[derive(EnumEntityEvent, Clone)]
+enum UnitEvent {
+    #[enum_event(derf)]
+    Damaged(Entity),
+    #[enum_event(auto_propogate)]
+    Healed { entity: Entity },
+    #[enum_event(prefix = "Unit")]
+    Died { entity: Entity },
+    #[enum_event(target)]
+    Moved { entity: Entity },
+    Renamed {
+        entity: Entity,
+        #[enum_event(defualt)]
+        name: String,
+        #[enum_event(rename = "title")]
+        label: String,
+    },
+    Spawned {
+        #[enum_event(colour)]
+        entity: Entity,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,95 @@
+//! Tests for the `VariantOf` trait linking each generated struct back to its source enum.
+
+mod common;
+
+use bevy::prelude::Entity;
+use bevy_enum_event::{EnumEntityEvent, EnumEvent, EnumMessage, VariantOf};
+use common::{game_event, GameEvent};
+
+fn describe<V: VariantOf<E>, E>(_variant: &V) -> String {
+    format!("{}::{}#{}", V::FAMILY, V::NAME, V::INDEX)
+}
+
+#[test]
+fn test_variant_metadata() {
+    assert_eq!(
+        describe(&game_event::Victory("red".to_string())),
+        "GameEvent::Victory#0"
+    );
+    assert_eq!(
+        describe(&game_event::ScoreChanged { team: 1, score: 2 }),
+        "GameEvent::ScoreChanged#1"
+    );
+    assert_eq!(describe(&game_event::GameOver), "GameEvent::GameOver#2");
+
+    assert_eq!(
+        <game_event::ScoreChanged as VariantOf<GameEvent>>::NAME,
+        "ScoreChanged"
+    );
+}
+
+#[test]
+fn test_into_family() {
+    fn collect<V: VariantOf<E>, E>(variants: Vec<V>) -> Vec<E> {
+        variants.into_iter().map(VariantOf::into_family).collect()
+    }
+
+    let events = collect(vec![
+        game_event::ScoreChanged { team: 1, score: 2 },
+        game_event::ScoreChanged { team: 2, score: 5 },
+    ]);
+    assert_eq!(
+        events,
+        vec![
+            GameEvent::ScoreChanged { team: 1, score: 2 },
+            GameEvent::ScoreChanged { team: 2, score: 5 },
+        ]
+    );
+}
+
+#[test]
+fn test_variant_of_other_kinds() {
+    #[derive(EnumMessage, Clone, Debug)]
+    #[allow(dead_code)]
+    enum NetworkCommand {
+        Connect { address: String },
+        Disconnect,
+    }
+
+    #[derive(EnumEntityEvent, Clone, Copy, Debug)]
+    #[allow(dead_code)]
+    enum PlayerEvent {
+        Damaged { entity: Entity, amount: u32 },
+    }
+
+    assert_eq!(
+        describe(&network_command::Disconnect),
+        "NetworkCommand::Disconnect#1"
+    );
+
+    let damaged = player_event::Damaged {
+        entity: Entity::PLACEHOLDER,
+        amount: 3,
+    };
+    assert_eq!(describe(&damaged), "PlayerEvent::Damaged#0");
+    assert!(matches!(
+        damaged.into_family(),
+        PlayerEvent::Damaged { amount: 3, .. }
+    ));
+}
+
+#[test]
+fn test_variant_of_generic_enum() {
+    #[derive(EnumEvent, Clone, Debug, PartialEq)]
+    #[allow(dead_code)]
+    enum Sample<T: Clone + std::fmt::Debug + PartialEq + Send + Sync + 'static> {
+        Value(T),
+        Reset,
+    }
+
+    assert_eq!(describe(&sample::Value(3_u32)), "Sample::Value#0");
+    assert_eq!(
+        sample::Reset::<u32>::new().into_family(),
+        Sample::<u32>::Reset
+    );
+}
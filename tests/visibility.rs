@@ -0,0 +1,119 @@
+//! Tests for generated items following the enum's visibility and `vis` overrides.
+
+use bevy::prelude::*;
+use bevy_enum_event::{EnumEvent, EnumMessage};
+
+mod internal {
+    use bevy_enum_event::{EnumEvent, EnumMessage};
+
+    // Crate-private payloads are fine in a crate-private family
+    #[derive(Clone, Debug, PartialEq)]
+    pub(crate) struct Secret(pub(crate) u32);
+
+    #[derive(EnumEvent, Clone, Debug, PartialEq)]
+    #[allow(dead_code)]
+    pub(crate) enum InternalEvent {
+        Found(Secret),
+        Lost { secret: Secret },
+    }
+
+    // Private family: only this module and its children can name the generated items
+    #[derive(Clone, Debug)]
+    struct Hidden;
+
+    #[derive(EnumMessage, Clone, Debug)]
+    #[allow(dead_code)]
+    enum HiddenMessage {
+        Payload(Hidden),
+    }
+
+    pub(crate) fn hidden_round_trip() -> bool {
+        let message = HiddenMessage::from(hidden_message::Payload(Hidden));
+        matches!(message.kind(), hidden_message::Kind::Payload)
+    }
+
+    pub(crate) mod nested {
+        use bevy_enum_event::EnumEvent;
+
+        #[derive(EnumEvent, Clone, Debug)]
+        #[allow(dead_code)]
+        pub(super) enum ParentOnly {
+            Ping,
+        }
+
+        #[derive(EnumEvent, Clone, Debug)]
+        #[allow(dead_code)]
+        pub(in crate::internal) enum PathRestricted {
+            Pong,
+        }
+    }
+
+    pub(crate) fn nested_round_trip() -> bool {
+        let ping = nested::ParentOnly::from(nested::parent_only::Ping);
+        let pong = nested::PathRestricted::from(nested::path_restricted::Pong);
+        ping.kind() == nested::parent_only::Kind::Ping
+            && pong.kind() == nested::path_restricted::Kind::Pong
+    }
+}
+
+#[test]
+fn test_crate_private_family() {
+    let found = internal::internal_event::Found(internal::Secret(3));
+    assert_eq!(found.0, internal::Secret(3));
+    let event: internal::InternalEvent = found.into();
+    assert_eq!(event, internal::InternalEvent::Found(internal::Secret(3)));
+    let _ = internal::internal_event::Plugin;
+}
+
+#[test]
+fn test_private_and_restricted_families() {
+    assert!(internal::hidden_round_trip());
+    assert!(internal::nested_round_trip());
+}
+
+// Overrides may widen or narrow, as long as the structs stay at least as visible as the enum
+#[derive(EnumMessage, Clone, Debug)]
+#[enum_event(vis = "pub(crate)")]
+#[allow(dead_code)]
+enum AppCommand {
+    Move {
+        x: f32,
+        #[enum_event(vis = "")]
+        y: f32,
+    },
+    #[enum_event(vis = "pub")]
+    Stop,
+}
+
+#[test]
+fn test_vis_overrides() {
+    let mut app = App::new();
+    app.add_plugins(app_command::Plugin);
+    app.add_systems(Update, |mut writer: app_command::Writer| {
+        writer.write(AppCommand::Move { x: 1.0, y: 2.0 });
+        writer.write(AppCommand::Stop);
+    });
+    app.update();
+
+    let moves = app.world().resource::<Messages<app_command::Move>>();
+    let xs: Vec<_> = moves.iter_current_update_messages().map(|m| m.x).collect();
+    assert_eq!(xs, vec![1.0]);
+    assert_eq!(
+        app.world().resource::<Messages<app_command::Stop>>().len(),
+        1
+    );
+}
+
+#[derive(EnumEvent, Clone, Debug)]
+#[enum_event(flat, suffix = "Event")]
+#[allow(dead_code)]
+enum PrivateFlat {
+    Tick(u32),
+}
+
+#[test]
+fn test_flat_keeps_enum_visibility() {
+    let tick = TickEvent(1);
+    assert_eq!(tick.0, 1);
+    assert_eq!(tick.kind(), PrivateFlatKind::Tick);
+}